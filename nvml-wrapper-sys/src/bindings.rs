@@ -10,7 +10,7 @@ pub const NVML_API_VERSION: raw::c_uint = 8;
 pub const NVML_API_VERSION_STR: &'static [u8; 2usize] = b"8\x00";
 pub const NVML_VALUE_NOT_AVAILABLE: raw::c_int = -1;
 pub const NVML_DEVICE_PCI_BUS_ID_BUFFER_SIZE: raw::c_uint = 16;
-pub const NVML_NVLINK_MAX_LINKS: raw::c_uint = 4;
+pub const NVML_NVLINK_MAX_LINKS: raw::c_uint = 18;
 pub const NVML_MAX_PHYSICAL_BRIDGE: raw::c_uint = 128;
 pub const nvmlFlagDefault: raw::c_uint = 0;
 pub const nvmlFlagForce: raw::c_uint = 1;
@@ -560,7 +560,12 @@ pub const nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_LONG: nvmlValueType_enum
 pub const nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_LONG_LONG:
           nvmlValueType_enum =
     3;
-pub const nvmlValueType_enum_NVML_VALUE_TYPE_COUNT: nvmlValueType_enum = 4;
+pub const nvmlValueType_enum_NVML_VALUE_TYPE_SIGNED_LONG_LONG:
+          nvmlValueType_enum =
+    4;
+pub const nvmlValueType_enum_NVML_VALUE_TYPE_SIGNED_INT: nvmlValueType_enum =
+    5;
+pub const nvmlValueType_enum_NVML_VALUE_TYPE_COUNT: nvmlValueType_enum = 6;
 pub type nvmlValueType_enum = raw::c_uint;
 pub use self::nvmlValueType_enum as nvmlValueType_t;
 #[repr(C)]
@@ -570,6 +575,8 @@ pub union nvmlValue_st {
     pub uiVal: raw::c_uint,
     pub ulVal: raw::c_ulong,
     pub ullVal: raw::c_ulonglong,
+    pub sllVal: raw::c_longlong,
+    pub siVal: raw::c_int,
 }
 #[test]
 fn bindgen_test_layout_nvmlValue_st() {
@@ -597,6 +604,16 @@ fn bindgen_test_layout_nvmlValue_st() {
                 usize } , 0usize , concat ! (
                 "Alignment of field: " , stringify ! ( nvmlValue_st ) , "::" ,
                 stringify ! ( ullVal ) ));
+    assert_eq! (unsafe {
+                & ( * ( 0 as * const nvmlValue_st ) ) . sllVal as * const _ as
+                usize } , 0usize , concat ! (
+                "Alignment of field: " , stringify ! ( nvmlValue_st ) , "::" ,
+                stringify ! ( sllVal ) ));
+    assert_eq! (unsafe {
+                & ( * ( 0 as * const nvmlValue_st ) ) . siVal as * const _ as
+                usize } , 0usize , concat ! (
+                "Alignment of field: " , stringify ! ( nvmlValue_st ) , "::" ,
+                stringify ! ( siVal ) ));
 }
 impl Clone for nvmlValue_st {
     fn clone(&self) -> Self { *self }
@@ -692,9 +709,27 @@ pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_SHUTDOWN:
 pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_SLOWDOWN:
           nvmlTemperatureThresholds_enum =
     1;
-pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_COUNT:
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_MEM_MAX:
           nvmlTemperatureThresholds_enum =
     2;
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_GPU_MAX:
+          nvmlTemperatureThresholds_enum =
+    3;
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MIN:
+          nvmlTemperatureThresholds_enum =
+    4;
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_CURR:
+          nvmlTemperatureThresholds_enum =
+    5;
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MAX:
+          nvmlTemperatureThresholds_enum =
+    6;
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_GPS_CURR:
+          nvmlTemperatureThresholds_enum =
+    7;
+pub const nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_COUNT:
+          nvmlTemperatureThresholds_enum =
+    8;
 pub type nvmlTemperatureThresholds_enum = raw::c_uint;
 pub use self::nvmlTemperatureThresholds_enum as nvmlTemperatureThresholds_t;
 pub const nvmlTemperatureSensors_enum_NVML_TEMPERATURE_GPU:
@@ -1853,3 +1888,407 @@ extern "C" {
     pub fn nvmlDeviceDiscoverGpus(pciInfo: *mut nvmlPciInfo_t)
      -> nvmlReturn_t;
 }
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct nvmlRowRemapperHistogramValues_st {
+    pub max: raw::c_uint,
+    pub high: raw::c_uint,
+    pub partial: raw::c_uint,
+    pub low: raw::c_uint,
+    pub none: raw::c_uint,
+}
+#[test]
+fn bindgen_test_layout_nvmlRowRemapperHistogramValues_st() {
+    assert_eq!(::std::mem::size_of::<nvmlRowRemapperHistogramValues_st>() ,
+               20usize , concat ! (
+               "Size of: " , stringify ! ( nvmlRowRemapperHistogramValues_st )
+               ));
+    assert_eq! (::std::mem::align_of::<nvmlRowRemapperHistogramValues_st>() ,
+                4usize , concat ! (
+                "Alignment of " , stringify ! (
+                nvmlRowRemapperHistogramValues_st ) ));
+}
+impl Clone for nvmlRowRemapperHistogramValues_st {
+    fn clone(&self) -> Self { *self }
+}
+pub type nvmlRowRemapperHistogramValues_t = nvmlRowRemapperHistogramValues_st;
+extern "C" {
+    pub fn nvmlDeviceGetRemappedRows(device: nvmlDevice_t,
+                                     corrRows: *mut raw::c_uint,
+                                     uncRows: *mut raw::c_uint,
+                                     isPending: *mut raw::c_uint,
+                                     failureOccurred: *mut raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetRowRemapperHistogram(device: nvmlDevice_t,
+                                             values:
+                                                 *mut nvmlRowRemapperHistogramValues_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetTotalEnergyConsumption(device: nvmlDevice_t,
+                                               energy: *mut raw::c_ulonglong)
+     -> nvmlReturn_t;
+}
+pub const NVML_FAN_POLICY_TEMPERATURE_CONTINOUS_SW: raw::c_uint = 0;
+pub const NVML_FAN_POLICY_MANUAL: raw::c_uint = 1;
+pub type nvmlFanControlPolicy_t = raw::c_uint;
+extern "C" {
+    pub fn nvmlDeviceGetNumFans(device: nvmlDevice_t,
+                                numFans: *mut raw::c_uint) -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetFanSpeed_v2(device: nvmlDevice_t, fan: raw::c_uint,
+                                    speed: *mut raw::c_uint) -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetTargetFanSpeed(device: nvmlDevice_t,
+                                       fan: raw::c_uint,
+                                       targetSpeed: *mut raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetMinMaxFanSpeed(device: nvmlDevice_t,
+                                       minSpeed: *mut raw::c_uint,
+                                       maxSpeed: *mut raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetFanControlPolicy_v2(device: nvmlDevice_t,
+                                            fan: raw::c_uint,
+                                            policy:
+                                                *mut nvmlFanControlPolicy_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetFanControlPolicy(device: nvmlDevice_t,
+                                         fan: raw::c_uint,
+                                         policy: nvmlFanControlPolicy_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetFanSpeed_v2(device: nvmlDevice_t, fan: raw::c_uint,
+                                    speed: raw::c_uint) -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetDefaultFanSpeed_v2(device: nvmlDevice_t,
+                                           fan: raw::c_uint) -> nvmlReturn_t;
+}
+pub const NVML_DEVICE_ARCH_KEPLER: raw::c_uint = 2;
+pub const NVML_DEVICE_ARCH_MAXWELL: raw::c_uint = 3;
+pub const NVML_DEVICE_ARCH_PASCAL: raw::c_uint = 4;
+pub const NVML_DEVICE_ARCH_VOLTA: raw::c_uint = 5;
+pub const NVML_DEVICE_ARCH_TURING: raw::c_uint = 6;
+pub const NVML_DEVICE_ARCH_AMPERE: raw::c_uint = 7;
+pub const NVML_DEVICE_ARCH_ADA: raw::c_uint = 8;
+pub const NVML_DEVICE_ARCH_HOPPER: raw::c_uint = 9;
+pub const NVML_DEVICE_ARCH_BLACKWELL: raw::c_uint = 10;
+pub const NVML_DEVICE_ARCH_UNKNOWN: raw::c_uint = 4294967295;
+pub type nvmlDeviceArchitecture_t = raw::c_uint;
+extern "C" {
+    pub fn nvmlDeviceGetArchitecture(device: nvmlDevice_t,
+                                     arch: *mut nvmlDeviceArchitecture_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetCudaComputeCapability(device: nvmlDevice_t,
+                                              major: *mut raw::c_int,
+                                              minor: *mut raw::c_int)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlSystemGetCudaDriverVersion(cudaDriverVersion: *mut raw::c_int)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetGpuLockedClocks(device: nvmlDevice_t,
+                                        minGpuClockMHz: raw::c_uint,
+                                        maxGpuClockMHz: raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceResetGpuLockedClocks(device: nvmlDevice_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetMemoryLockedClocks(device: nvmlDevice_t,
+                                           minMemClockMHz: raw::c_uint,
+                                           maxMemClockMHz: raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceResetMemoryLockedClocks(device: nvmlDevice_t)
+     -> nvmlReturn_t;
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct nvmlClockOffset_v1_t {
+    pub version: raw::c_uint,
+    pub type_: nvmlClockType_t,
+    pub pstate: nvmlPstates_t,
+    pub clockOffsetMHz: raw::c_int,
+    pub minClockOffsetMHz: raw::c_int,
+    pub maxClockOffsetMHz: raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_nvmlClockOffset_v1_t() {
+    assert_eq!(::std::mem::size_of::<nvmlClockOffset_v1_t>() , 24usize ,
+               concat ! ( "Size of: " , stringify ! ( nvmlClockOffset_v1_t )
+               ));
+    assert_eq! (::std::mem::align_of::<nvmlClockOffset_v1_t>() , 4usize ,
+                concat ! (
+                "Alignment of " , stringify ! ( nvmlClockOffset_v1_t ) ));
+}
+impl Clone for nvmlClockOffset_v1_t {
+    fn clone(&self) -> Self { *self }
+}
+pub type nvmlClockOffset_t = nvmlClockOffset_v1_t;
+/// `NVML_STRUCT_VERSION(ClockOffset, 1)`
+pub const nvmlClockOffset_v1: raw::c_uint = 16777240;
+extern "C" {
+    pub fn nvmlDeviceGetClockOffsets(device: nvmlDevice_t,
+                                     info: *mut nvmlClockOffset_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetClockOffsets(device: nvmlDevice_t,
+                                     info: *mut nvmlClockOffset_t)
+     -> nvmlReturn_t;
+}
+pub const NVML_MAX_THERMAL_SENSORS_PER_GPU: raw::c_uint = 3;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE: nvmlThermalTarget_t =
+    0;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU: nvmlThermalTarget_t =
+    1;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY: nvmlThermalTarget_t
+          =
+    2;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY:
+          nvmlThermalTarget_t =
+    4;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD: nvmlThermalTarget_t =
+    8;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD:
+          nvmlThermalTarget_t =
+    9;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET:
+          nvmlThermalTarget_t =
+    10;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET:
+          nvmlThermalTarget_t =
+    11;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL: nvmlThermalTarget_t =
+    15;
+pub const nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN: nvmlThermalTarget_t
+          =
+    -1;
+pub type nvmlThermalTarget_t = raw::c_int;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE:
+          nvmlThermalController_t =
+    0;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL:
+          nvmlThermalController_t =
+    1;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032:
+          nvmlThermalController_t =
+    2;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461:
+          nvmlThermalController_t =
+    3;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649:
+          nvmlThermalController_t =
+    4;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617:
+          nvmlThermalController_t =
+    5;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99:
+          nvmlThermalController_t =
+    6;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89:
+          nvmlThermalController_t =
+    7;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64:
+          nvmlThermalController_t =
+    8;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781:
+          nvmlThermalController_t =
+    9;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473:
+          nvmlThermalController_t =
+    10;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649:
+          nvmlThermalController_t =
+    11;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT:
+          nvmlThermalController_t =
+    12;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS:
+          nvmlThermalController_t =
+    13;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS:
+          nvmlThermalController_t =
+    14;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551:
+          nvmlThermalController_t =
+    15;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R:
+          nvmlThermalController_t =
+    16;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S:
+          nvmlThermalController_t =
+    17;
+pub const nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN:
+          nvmlThermalController_t =
+    -1;
+pub type nvmlThermalController_t = raw::c_int;
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct nvmlGpuThermalSettings_t__bindgen_ty_1 {
+    pub controller: nvmlThermalController_t,
+    pub defaultMinTemp: raw::c_int,
+    pub defaultMaxTemp: raw::c_int,
+    pub currentTemp: raw::c_int,
+    pub target: nvmlThermalTarget_t,
+}
+#[test]
+fn bindgen_test_layout_nvmlGpuThermalSettings_t__bindgen_ty_1() {
+    assert_eq!(::std::mem::size_of::<nvmlGpuThermalSettings_t__bindgen_ty_1>()
+               , 20usize , concat ! (
+               "Size of: " , stringify ! (
+               nvmlGpuThermalSettings_t__bindgen_ty_1 ) ));
+    assert_eq! (::std::mem::align_of::<nvmlGpuThermalSettings_t__bindgen_ty_1>()
+                , 4usize , concat ! (
+                "Alignment of " , stringify ! (
+                nvmlGpuThermalSettings_t__bindgen_ty_1 ) ));
+}
+impl Clone for nvmlGpuThermalSettings_t__bindgen_ty_1 {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct nvmlGpuThermalSettings_t {
+    pub count: raw::c_uint,
+    pub sensor: [nvmlGpuThermalSettings_t__bindgen_ty_1; 3usize],
+}
+#[test]
+fn bindgen_test_layout_nvmlGpuThermalSettings_t() {
+    assert_eq!(::std::mem::size_of::<nvmlGpuThermalSettings_t>() , 64usize ,
+               concat ! (
+               "Size of: " , stringify ! ( nvmlGpuThermalSettings_t ) ));
+    assert_eq! (::std::mem::align_of::<nvmlGpuThermalSettings_t>() , 4usize ,
+                concat ! (
+                "Alignment of " , stringify ! ( nvmlGpuThermalSettings_t ) ));
+}
+impl Clone for nvmlGpuThermalSettings_t {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn nvmlDeviceGetThermalSettings(device: nvmlDevice_t,
+                                        sensorIndex: raw::c_uint,
+                                        pThermalSettings:
+                                            *mut nvmlGpuThermalSettings_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceSetTemperatureThreshold(device: nvmlDevice_t,
+                                             thresholdType:
+                                                 nvmlTemperatureThresholds_t,
+                                             temp: *mut raw::c_int)
+     -> nvmlReturn_t;
+}
+pub const NVML_FI_DEV_MEMORY_TEMP: raw::c_uint = 82;
+#[repr(C)]
+#[derive(Copy)]
+pub struct nvmlFieldValue_st {
+    pub fieldId: raw::c_uint,
+    pub scopeId: raw::c_uint,
+    pub timestamp: raw::c_longlong,
+    pub latencyUsec: raw::c_longlong,
+    pub valueType: nvmlValueType_t,
+    pub nvmlReturn: nvmlReturn_t,
+    pub value: nvmlValue_t,
+}
+#[test]
+fn bindgen_test_layout_nvmlFieldValue_st() {
+    assert_eq!(::std::mem::size_of::<nvmlFieldValue_st>() , 40usize , concat !
+               ( "Size of: " , stringify ! ( nvmlFieldValue_st ) ));
+    assert_eq! (::std::mem::align_of::<nvmlFieldValue_st>() , 8usize , concat
+                ! ( "Alignment of " , stringify ! ( nvmlFieldValue_st ) ));
+}
+impl Clone for nvmlFieldValue_st {
+    fn clone(&self) -> Self { *self }
+}
+pub type nvmlFieldValue_t = nvmlFieldValue_st;
+extern "C" {
+    pub fn nvmlDeviceGetFieldValues(device: nvmlDevice_t,
+                                    valuesCount: raw::c_int,
+                                    values: *mut nvmlFieldValue_t)
+     -> nvmlReturn_t;
+}
+pub const NVML_PCIE_LINK_MAX_SPEED_INVALID: raw::c_uint = 0;
+pub const NVML_PCIE_LINK_MAX_SPEED_2500MBPS: raw::c_uint = 1;
+pub const NVML_PCIE_LINK_MAX_SPEED_5000MBPS: raw::c_uint = 2;
+pub const NVML_PCIE_LINK_MAX_SPEED_8000MBPS: raw::c_uint = 3;
+pub const NVML_PCIE_LINK_MAX_SPEED_16000MBPS: raw::c_uint = 4;
+pub const NVML_PCIE_LINK_MAX_SPEED_32000MBPS: raw::c_uint = 5;
+pub const NVML_PCIE_LINK_MAX_SPEED_64000MBPS: raw::c_uint = 6;
+pub const NVML_BUS_TYPE_UNKNOWN: raw::c_uint = 0;
+pub const NVML_BUS_TYPE_PCI: raw::c_uint = 1;
+pub const NVML_BUS_TYPE_PCIE: raw::c_uint = 2;
+pub const NVML_BUS_TYPE_FPCI: raw::c_uint = 3;
+pub const NVML_BUS_TYPE_AGP: raw::c_uint = 4;
+pub type nvmlBusType_t = raw::c_uint;
+extern "C" {
+    pub fn nvmlDeviceGetPcieLinkMaxSpeed(device: nvmlDevice_t,
+                                         maxSpeed: *mut raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetPcieSpeed(device: nvmlDevice_t,
+                                  pcieSpeed: *mut raw::c_uint)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetBusType(device: nvmlDevice_t,
+                                type_: *mut nvmlBusType_t) -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetGpuMaxPcieLinkGeneration(device: nvmlDevice_t,
+                                                 maxLinkGenDevice:
+                                                     *mut raw::c_uint)
+     -> nvmlReturn_t;
+}
+pub const NVML_AFFINITY_SCOPE_NODE: raw::c_uint = 0;
+pub const NVML_AFFINITY_SCOPE_SOCKET: raw::c_uint = 1;
+pub type nvmlAffinityScope_t = raw::c_uint;
+extern "C" {
+    pub fn nvmlDeviceGetMemoryAffinity(device: nvmlDevice_t,
+                                       nodeSetSize: raw::c_uint,
+                                       nodeSet: *mut raw::c_ulong,
+                                       scope: nvmlAffinityScope_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetCpuAffinityWithinScope(device: nvmlDevice_t,
+                                               cpuSetSize: raw::c_uint,
+                                               cpuSet: *mut raw::c_ulong,
+                                               scope: nvmlAffinityScope_t)
+     -> nvmlReturn_t;
+}
+extern "C" {
+    pub fn nvmlDeviceGetNumaNodeId(device: nvmlDevice_t,
+                                   node: *mut raw::c_uint) -> nvmlReturn_t;
+}
+pub const NVML_NVLINK_DEVICE_TYPE_GPU: raw::c_uint = 0;
+pub const NVML_NVLINK_DEVICE_TYPE_IBMNPU: raw::c_uint = 1;
+pub const NVML_NVLINK_DEVICE_TYPE_SWITCH: raw::c_uint = 2;
+pub const NVML_NVLINK_DEVICE_TYPE_UNKNOWN: raw::c_uint = 255;
+pub type nvmlIntNvLinkDeviceType_t = raw::c_uint;
+extern "C" {
+    pub fn nvmlDeviceGetNvLinkRemoteDeviceType(device: nvmlDevice_t,
+                                               link: raw::c_uint,
+                                               pNvLinkDeviceType:
+                                                   *mut nvmlIntNvLinkDeviceType_t)
+     -> nvmlReturn_t;
+}
@@ -63,6 +63,11 @@ These bindings were generated for NVML version 8. Each new version of NVML is
 guaranteed to be backwards-compatible according to NVIDIA, so these bindings
 should be useful regardless of NVML version bumps.
 
+Declarations for a number of functions and types introduced in later versions
+of NVML have been added to the end of the generated bindings by hand. Calling
+one of these on a system with an older driver will fail at link or load time,
+so check the NVML version your users have installed before relying on them.
+
 ## Rust Version Support
 
 Currently supports Rust 1.19.0 or greater. The target version is the **latest**
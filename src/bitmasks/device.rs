@@ -1,6 +1,8 @@
 #![allow(deprecated)]
 
+use bitmasks::write_flags;
 use ffi::bindings::*;
+use std::fmt;
 
 bitflags! {
     /// Flags used to specify why a GPU is throttling.
@@ -44,3 +46,37 @@ bitflags! {
         const NONE                        = nvmlClocksThrottleReasonNone as u64;
     }
 }
+
+impl fmt::Display for ThrottleReasons {
+    /// Lists the reasons using nvidia-smi's names, e.g. `SW Power Cap, HW Slowdown`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(
+            f,
+            self.bits(),
+            &[
+                (ThrottleReasons::GPU_IDLE.bits(), "GPU Idle"),
+                (ThrottleReasons::APPLICATIONS_CLOCKS_SETTING.bits(), "Applications Clocks Setting"),
+                (ThrottleReasons::SW_POWER_CAP.bits(), "SW Power Cap"),
+                (ThrottleReasons::HW_SLOWDOWN.bits(), "HW Slowdown"),
+                (ThrottleReasons::SYNC_BOOST.bits(), "Sync Boost"),
+                (ThrottleReasons::UNKNOWN.bits(), "Unknown"),
+            ],
+            "None"
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn throttle_reasons_display() {
+        assert_eq!(ThrottleReasons::NONE.to_string(), "None");
+        assert_eq!(ThrottleReasons::SW_POWER_CAP.to_string(), "SW Power Cap");
+        assert_eq!(
+            (ThrottleReasons::GPU_IDLE | ThrottleReasons::HW_SLOWDOWN).to_string(),
+            "GPU Idle, HW Slowdown"
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use bitmasks::write_flags;
 use ffi::bindings::*;
+use std::fmt;
 
 bitflags! {
     /**
@@ -31,3 +33,20 @@ bitflags! {
         const CLOCK_CHANGE          = nvmlEventTypeClock as u64;
     }
 }
+
+impl fmt::Display for EventTypes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(
+            f,
+            self.bits(),
+            &[
+                (EventTypes::SINGLE_BIT_ECC_ERROR.bits(), "Single Bit ECC Error"),
+                (EventTypes::DOUBLE_BIT_ECC_ERROR.bits(), "Double Bit ECC Error"),
+                (EventTypes::PSTATE_CHANGE.bits(), "PState Change"),
+                (EventTypes::CRITICAL_XID_ERROR.bits(), "Critical XID Error"),
+                (EventTypes::CLOCK_CHANGE.bits(), "Clock Change"),
+            ],
+            "None"
+        )
+    }
+}
@@ -3,6 +3,7 @@ pub mod nv_link;
 pub mod event;
 
 use ffi::bindings::*;
+use std::fmt;
 
 bitflags! {
     /// Generic flags used to specify the default behavior of some functions.
@@ -13,3 +14,186 @@ bitflags! {
         const FORCE   = nvmlFlagForce;
     }
 }
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(f, u64::from(self.bits()), &[(Behavior::FORCE.bits().into(), "Force")], "Default")
+    }
+}
+
+/**
+Implemented by the bitflags types in this module so that `Lossless` can split a
+raw bitmask into the flags this wrapper knows about and the bits it does not.
+*/
+pub trait Flags: Copy {
+    /// The bits of every flag this version of the wrapper knows about.
+    fn known_bits() -> u64;
+    /// Creates flags from `bits`, dropping any that are not known.
+    fn from_known_bits(bits: u64) -> Self;
+    /// The bits of the flags that are set.
+    fn to_bits(&self) -> u64;
+}
+
+macro_rules! impl_flags {
+    ($($name:ty),+) => {
+        $(
+            impl Flags for $name {
+                #[inline]
+                fn known_bits() -> u64 {
+                    Self::all().bits() as u64
+                }
+
+                #[inline]
+                fn from_known_bits(bits: u64) -> Self {
+                    Self::from_bits_truncate((bits & Self::known_bits()) as _)
+                }
+
+                #[inline]
+                fn to_bits(&self) -> u64 {
+                    self.bits() as u64
+                }
+            }
+        )+
+    };
+}
+
+impl_flags!(device::ThrottleReasons, event::EventTypes, nv_link::PacketTypes);
+
+/**
+A bitmask split into the flags this wrapper knows about and the bits it does
+not.
+
+Newer drivers can report flags that did not exist when this wrapper was
+written. Methods and structs that deal with such bitmasks return them here
+instead of dropping them, so that they can be logged or exported.
+*/
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lossless<T> {
+    /// The flags this wrapper knows about.
+    pub flags: T,
+    /// Every bit that does not correspond to a flag in `T`.
+    pub unknown_bits: u64
+}
+
+impl<T: Flags> Lossless<T> {
+    /// Splits a raw bitmask.
+    #[inline]
+    pub fn from_bits(bits: u64) -> Self {
+        Lossless {
+            flags: T::from_known_bits(bits),
+            unknown_bits: bits & !T::known_bits()
+        }
+    }
+
+    /// The raw bitmask, including unknown bits.
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        self.flags.to_bits() | self.unknown_bits
+    }
+
+    /// Whether any bits are not known to this wrapper.
+    #[inline]
+    pub fn has_unknown_bits(&self) -> bool {
+        self.unknown_bits != 0
+    }
+}
+
+impl<T: Flags> From<T> for Lossless<T> {
+    fn from(flags: T) -> Self {
+        Lossless {
+            flags,
+            unknown_bits: 0
+        }
+    }
+}
+
+impl<T: Flags + fmt::Display> fmt::Display for Lossless<T> {
+    /// Writes the known flags followed by the unknown bits, if any, e.g.
+    /// `SW Power Cap, Unknown (0x400)`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.flags.to_bits(), self.unknown_bits) {
+            (_, 0) => write!(f, "{}", self.flags),
+            (0, unknown) => write!(f, "Unknown ({:#x})", unknown),
+            (_, unknown) => write!(f, "{}, Unknown ({:#x})", self.flags, unknown),
+        }
+    }
+}
+
+/*
+Writes the names of the flags set in `bits`, joined by commas, followed by any
+bits that do not correspond to a named flag. `empty` is written if no bits are
+set.
+*/
+pub(crate) fn write_flags(
+    f: &mut fmt::Formatter,
+    bits: u64,
+    names: &[(u64, &str)],
+    empty: &str,
+) -> fmt::Result {
+    if bits == 0 {
+        return f.write_str(empty);
+    }
+
+    let mut remaining = bits;
+    let mut first = true;
+    let mut separator = |f: &mut fmt::Formatter| {
+        if first {
+            first = false;
+            Ok(())
+        } else {
+            f.write_str(", ")
+        }
+    };
+
+    for &(flag, name) in names {
+        if flag != 0 && bits & flag == flag {
+            separator(f)?;
+            f.write_str(name)?;
+            remaining &= !flag;
+        }
+    }
+
+    if remaining != 0 {
+        separator(f)?;
+        write!(f, "Unknown ({:#x})", remaining)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bitmasks::device::ThrottleReasons;
+    use bitmasks::nv_link::PacketTypes;
+
+    #[test]
+    fn lossless_keeps_unknown_bits() {
+        let bits = ThrottleReasons::SW_POWER_CAP.bits() | 0x1_0000_0000;
+        let reasons = Lossless::<ThrottleReasons>::from_bits(bits);
+
+        assert_eq!(reasons.flags, ThrottleReasons::SW_POWER_CAP);
+        assert_eq!(reasons.unknown_bits, 0x1_0000_0000);
+        assert!(reasons.has_unknown_bits());
+        assert_eq!(reasons.bits(), bits);
+        assert_eq!(reasons.to_string(), "SW Power Cap, Unknown (0x100000000)");
+    }
+
+    #[test]
+    fn lossless_bits_wider_than_flags() {
+        let packets = Lossless::<PacketTypes>::from_bits(PacketTypes::READ.bits() as u64 | 1 << 40);
+
+        assert_eq!(packets.flags, PacketTypes::READ);
+        assert_eq!(packets.unknown_bits, 1 << 40);
+    }
+
+    #[test]
+    fn lossless_from_flags() {
+        let reasons = Lossless::from(ThrottleReasons::GPU_IDLE);
+
+        assert!(!reasons.has_unknown_bits());
+        assert_eq!(reasons.to_string(), "GPU Idle");
+        assert_eq!(Lossless::<ThrottleReasons>::from_bits(0x1000_0000_0000).to_string(), "Unknown (0x100000000000)");
+    }
+}
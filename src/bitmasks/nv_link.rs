@@ -1,4 +1,6 @@
+use bitmasks::write_flags;
 use ffi::bindings::*;
+use std::fmt;
 
 bitflags! {
     /**
@@ -25,3 +27,23 @@ bitflags! {
         const NO_DATA    = nvmlNvLinkUtilizationCountPktTypes_enum_NVML_NVLINK_COUNTER_PKTFILTER_RESPNODATA as u32;
     }
 }
+
+impl fmt::Display for PacketTypes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_flags(
+            f,
+            u64::from(self.bits()),
+            &[
+                (PacketTypes::NO_OP.bits().into(), "NOP"),
+                (PacketTypes::READ.bits().into(), "Read"),
+                (PacketTypes::WRITE.bits().into(), "Write"),
+                (PacketTypes::RATOM.bits().into(), "Reduction Atomic"),
+                (PacketTypes::NON_RATOM.bits().into(), "Non-Reduction Atomic"),
+                (PacketTypes::FLUSH.bits().into(), "Flush"),
+                (PacketTypes::WITH_DATA.bits().into(), "Response With Data"),
+                (PacketTypes::NO_DATA.bits().into(), "Response Without Data"),
+            ],
+            "None"
+        )
+    }
+}
@@ -2,15 +2,19 @@
 use EventSet;
 use NVML;
 use NvLink;
+use SampleCursor;
 #[cfg(target_os = "windows")]
 use bitmasks::Behavior;
+use bitmasks::Lossless;
 use bitmasks::device::ThrottleReasons;
 #[cfg(target_os = "linux")]
 use bitmasks::event::EventTypes;
 use enum_wrappers::{state_from_bool, bool_from_state};
 use enum_wrappers::device::*;
+use enums::device::SampleValue;
 use error::{Bits, nvml_try, Result, ResultExt, ErrorKind, Error};
 use ffi::bindings::*;
+use std::cmp;
 use std::ffi::CStr;
 use std::marker::PhantomData;
 use std::mem;
@@ -18,6 +22,8 @@ use std::os::raw::{c_int, c_uint, c_ulong, c_ulonglong};
 use std::ptr;
 use struct_wrappers::device::*;
 use structs::device::*;
+use units::TypedDevice;
+use utils::optional;
 
 /**
 Struct that represents a device on the system. 
@@ -229,6 +235,71 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the architecture of this `Device`.
+
+    Architectures that this wrapper does not recognize are returned as
+    `DeviceArchitecture::Unknown`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn architecture(&self) -> Result<DeviceArchitecture> {
+        unsafe {
+            let mut arch: nvmlDeviceArchitecture_t = mem::zeroed();
+            nvml_try(nvmlDeviceGetArchitecture(self.device, &mut arch))?;
+
+            Ok(arch.into())
+        }
+    }
+
+    /**
+    Gets the CUDA compute capability of this `Device`.
+
+    The returned version numbers are the same as those returned by
+    `cudaGetDeviceProperties()` in the CUDA runtime (e.g. 7.0 for Volta).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn cuda_compute_capability(&self) -> Result<CudaComputeCapability> {
+        unsafe {
+            let mut major: c_int = mem::zeroed();
+            let mut minor: c_int = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetCudaComputeCapability(
+                self.device,
+                &mut major,
+                &mut minor
+            ))?;
+
+            Ok(CudaComputeCapability {
+                major,
+                minor
+            })
+        }
+    }
+
     /**
     Gets the board ID for this `Device`, from 0-N. 
     
@@ -276,7 +347,6 @@ impl<'nvml> Device<'nvml> {
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if the device is invalid
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, check that error's docs for more info
     * `Unknown`, on any unexpected error
     */
     // Checked against local nvml.h
@@ -287,7 +357,7 @@ impl<'nvml> Device<'nvml> {
             let mut brand: nvmlBrandType_t = mem::zeroed();
             nvml_try(nvmlDeviceGetBrand(self.device, &mut brand))?;
 
-            Ok(Brand::try_from(brand)?)
+            Ok(Brand::from(brand))
         }
     }
 
@@ -302,7 +372,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if the device is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
     
     # Device Support
@@ -317,7 +386,7 @@ impl<'nvml> Device<'nvml> {
             let mut info: nvmlBridgeChipHierarchy_t = mem::zeroed();
             nvml_try(nvmlDeviceGetBridgeChipInfo(self.device, &mut info))?;
 
-            Ok(BridgeChipHierarchy::try_from(info)?)
+            Ok(BridgeChipHierarchy::from(info))
         }
     }
 
@@ -397,7 +466,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if this `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, check that error's docs for more info
     * `Unknown`, on any unexpected error
     */
     // Checked against local
@@ -408,7 +476,7 @@ impl<'nvml> Device<'nvml> {
             let mut mode: nvmlComputeMode_t = mem::zeroed();
             nvml_try(nvmlDeviceGetComputeMode(self.device, &mut mode))?;
 
-            Ok(ComputeMode::try_from(mode)?)
+            Ok(ComputeMode::from(mode))
         }
     }
 
@@ -558,6 +626,151 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the ideal CPU affinity for the device, decoded into a `CpuSet`.
+
+    `size` is the number of bitmask words to request, as with `.cpu_affinity()`;
+    it must be large enough to cover every CPU in the system.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `InsufficientSize`, if the passed-in `size` is 0 (must be > 0)
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    // Checked against local
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn cpu_affinity_set(&self, size: usize) -> Result<CpuSet> {
+        Ok(CpuSet::from_bitmask(&self.cpu_affinity(size)?))
+    }
+
+    /**
+    Gets the ideal CPU affinity for the device, limited to the NUMA node or CPU
+    socket (depending on `scope`) closest to it.
+
+    `size` is the number of bitmask words to request, as with `.cpu_affinity()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `InsufficientSize`, if the passed-in `size` is 0 (must be > 0)
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    // Checked against local
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn cpu_affinity_within_scope(&self, size: usize, scope: AffinityScope) -> Result<CpuSet> {
+        unsafe {
+            if size == 0 {
+                // Return an error containing the minimum size that can be passed.
+                bail!(ErrorKind::InsufficientSize(Some(1)));
+            }
+
+            let mut affinities: Vec<c_ulong> = vec![mem::zeroed(); size];
+
+            nvml_try(nvmlDeviceGetCpuAffinityWithinScope(
+                self.device,
+                size as c_uint,
+                affinities.as_mut_ptr(),
+                scope.as_c()
+            ))?;
+
+            Ok(CpuSet::from_bitmask(&affinities))
+        }
+    }
+
+    /**
+    Gets the NUMA nodes whose memory is closest to the device, limited to the
+    NUMA node or CPU socket (depending on `scope`) closest to it.
+
+    `size` is the number of bitmask words to request; 64 nodes per unsigned long
+    on 64-bit machines, 32 on 32-bit machines.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `InsufficientSize`, if the passed-in `size` is 0 (must be > 0)
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    // Checked against local
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn memory_affinity(&self, size: usize, scope: AffinityScope) -> Result<NodeSet> {
+        unsafe {
+            if size == 0 {
+                // Return an error containing the minimum size that can be passed.
+                bail!(ErrorKind::InsufficientSize(Some(1)));
+            }
+
+            let mut affinities: Vec<c_ulong> = vec![mem::zeroed(); size];
+
+            nvml_try(nvmlDeviceGetMemoryAffinity(
+                self.device,
+                size as c_uint,
+                affinities.as_mut_ptr(),
+                scope.as_c()
+            ))?;
+
+            Ok(NodeSet::from_bitmask(&affinities))
+        }
+    }
+
+    /**
+    Gets the NUMA node this `Device` is attached to.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    #[inline]
+    pub fn numa_node(&self) -> Result<u32> {
+        unsafe {
+            let mut node: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetNumaNodeId(self.device, &mut node))?;
+
+            Ok(node)
+        }
+    }
+
     /**
     Gets the current PCIe link generation.
     
@@ -778,7 +991,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if the device is invalid
     * `NotSupported`, if the platform is not Windows
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
     
     # Device Support
@@ -805,8 +1017,8 @@ impl<'nvml> Device<'nvml> {
             ))?;
 
             Ok(DriverModelState {
-                current: DriverModel::try_from(current)?,
-                pending: DriverModel::try_from(pending)?
+                current: DriverModel::from(current),
+                pending: DriverModel::from(pending)
             })
         }
     }
@@ -951,112 +1163,260 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Gets the current GPU operation mode and the pending one (that it will switch to
-    after a reboot).
-    
+    Gets the intended operating speed of the specified fan as a percentage of the
+    maximum fan speed (100%).
+
+    Note: The reported speed is the intended fan speed. If the fan is physically blocked
+    and unable to spin, the output will not match the actual fan speed.
+
+    You can determine valid fan indices using `.num_fans()`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the device is invalid
-    * `NotSupported`, if this `Device` does not support this feature
+    * `InvalidArg`, if the device is invalid or `fan_idx` is invalid
+    * `NotSupported`, if this `Device` does not have a fan
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
-    
+
     # Device Support
 
-    Supports GK110 M-class and X-class Tesla products from the Kepler family. Modes `LowDP`
-    and `AllOn` are supported on fully supported GeForce products. Not supported
-    on Quadro and Tesla C-class products.
+    Supports all discrete products with dedicated fans.
     */
     // Checked against local
-    // Tested on machines other than my own
     #[inline]
-    pub fn gpu_operation_mode(&self) -> Result<OperationModeState> {
+    pub fn fan_speed_for(&self, fan_idx: u32) -> Result<u32> {
         unsafe {
-            let mut current: nvmlGpuOperationMode_t = mem::zeroed();
-            let mut pending: nvmlGpuOperationMode_t = mem::zeroed();
-
-            nvml_try(nvmlDeviceGetGpuOperationMode(
-                self.device,
-                &mut current,
-                &mut pending
-            ))?;
+            let mut speed: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetFanSpeed_v2(self.device, fan_idx, &mut speed))?;
 
-            Ok(OperationModeState {
-                current: OperationMode::try_from(current)?,
-                pending: OperationMode::try_from(pending)?
-            })
+            Ok(speed)
         }
     }
 
     /**
-    Gets information about processes with a graphics context running on this `Device`.
-    
-    This only returns information about graphics based processes (OpenGL, DirectX).
-    
+    Gets the speed that the specified fan is being driven towards as a percentage of
+    the maximum fan speed (100%).
+
+    This differs from `.fan_speed_for()` in that it reports the speed the fan controller
+    is aiming for rather than the speed the fan is currently being driven at.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the device is invalid
+    * `InvalidArg`, if the device is invalid or `fan_idx` is invalid
+    * `NotSupported`, if this `Device` does not have a fan
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all discrete products with dedicated fans.
     */
-    // Tested
+    // Checked against local
     #[inline]
-    pub fn running_graphics_processes(&self) -> Result<Vec<ProcessInfo>> {
+    pub fn target_fan_speed(&self, fan_idx: u32) -> Result<u32> {
         unsafe {
-            let mut count: c_uint = match self.running_graphics_processes_count()? {
-                0 => return Ok(vec![]),
-                value => value,
-            };
-            let mut processes: Vec<nvmlProcessInfo_t> = vec![mem::zeroed(); count as usize];
-
-            nvml_try(nvmlDeviceGetGraphicsRunningProcesses(
-                self.device,
-                &mut count,
-                processes.as_mut_ptr()
-            ))?;
+            let mut speed: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetTargetFanSpeed(self.device, fan_idx, &mut speed))?;
 
-            Ok(processes.iter().map(|p| ProcessInfo::from(*p)).collect())
+            Ok(speed)
         }
     }
 
     /**
-    Gets the number of processes with a graphics context running on this `Device`.
-    
-    This only returns the count of graphics based processes (OpenGL, DirectX).
-    
+    Gets the number of fans on this `Device`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if the device is invalid
-    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
+    * `NotSupported`, if this `Device` does not have a fan
     * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all discrete products with dedicated fans.
     */
-    // Tested as part of `.running_graphics_processes()`
+    // Checked against local
     #[inline]
-    pub fn running_graphics_processes_count(&self) -> Result<u32> {
+    pub fn num_fans(&self) -> Result<u32> {
         unsafe {
-            // Indicates that we want the count
-            let mut count: c_uint = 0;
+            let mut count: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetNumFans(self.device, &mut count))?;
 
-            // Passing null doesn't indicate that we want the count. It's just allowed.
-            match nvmlDeviceGetGraphicsRunningProcesses(self.device, &mut count, ptr::null_mut()) {
-                nvmlReturn_enum_NVML_SUCCESS => Ok(0),
-                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
-                // We know that this will be an error
-                other => nvml_try(other).map(|_| 0),
-            }
+            Ok(count)
         }
     }
 
     /**
-    Gets the NVML index of this `Device`. 
-    
-    Keep in mind that the order in which NVML enumerates devices has no guarantees of
-    consistency between reboots. Also, the NVML index may not correlate with other APIs,
+    Gets the range of speeds (as percentages of the maximum fan speed) that this
+    `Device`'s fans can be set to.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not have a fan
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all discrete products with dedicated fans.
+    */
+    // Checked against local
+    #[inline]
+    pub fn min_max_fan_speed(&self) -> Result<FanSpeedConstraints> {
+        unsafe {
+            let mut min_speed: c_uint = mem::zeroed();
+            let mut max_speed: c_uint = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetMinMaxFanSpeed(
+                self.device,
+                &mut min_speed,
+                &mut max_speed
+            ))?;
+
+            Ok(FanSpeedConstraints {
+                min_speed,
+                max_speed
+            })
+        }
+    }
+
+    /**
+    Gets the control policy of the specified fan.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid or `fan_idx` is invalid
+    * `NotSupported`, if this `Device` does not have a fan
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported discrete devices with fans.
+    */
+    // Checked against local
+    #[inline]
+    pub fn fan_control_policy(&self, fan_idx: u32) -> Result<FanControlPolicy> {
+        unsafe {
+            let mut policy: nvmlFanControlPolicy_t = mem::zeroed();
+            nvml_try(nvmlDeviceGetFanControlPolicy_v2(self.device, fan_idx, &mut policy))?;
+
+            Ok(policy.into())
+        }
+    }
+
+    /**
+    Gets the current GPU operation mode and the pending one (that it will switch to
+    after a reboot).
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    
+    # Device Support
+
+    Supports GK110 M-class and X-class Tesla products from the Kepler family. Modes `LowDP`
+    and `AllOn` are supported on fully supported GeForce products. Not supported
+    on Quadro and Tesla C-class products.
+    */
+    // Checked against local
+    // Tested on machines other than my own
+    #[inline]
+    pub fn gpu_operation_mode(&self) -> Result<OperationModeState> {
+        unsafe {
+            let mut current: nvmlGpuOperationMode_t = mem::zeroed();
+            let mut pending: nvmlGpuOperationMode_t = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetGpuOperationMode(
+                self.device,
+                &mut current,
+                &mut pending
+            ))?;
+
+            Ok(OperationModeState {
+                current: OperationMode::from(current),
+                pending: OperationMode::from(pending)
+            })
+        }
+    }
+
+    /**
+    Gets information about processes with a graphics context running on this `Device`.
+    
+    This only returns information about graphics based processes (OpenGL, DirectX).
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    #[inline]
+    pub fn running_graphics_processes(&self) -> Result<Vec<ProcessInfo>> {
+        unsafe {
+            let mut count: c_uint = match self.running_graphics_processes_count()? {
+                0 => return Ok(vec![]),
+                value => value,
+            };
+            let mut processes: Vec<nvmlProcessInfo_t> = vec![mem::zeroed(); count as usize];
+
+            nvml_try(nvmlDeviceGetGraphicsRunningProcesses(
+                self.device,
+                &mut count,
+                processes.as_mut_ptr()
+            ))?;
+
+            Ok(processes.iter().map(|p| ProcessInfo::from(*p)).collect())
+        }
+    }
+
+    /**
+    Gets the number of processes with a graphics context running on this `Device`.
+    
+    This only returns the count of graphics based processes (OpenGL, DirectX).
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    // Tested as part of `.running_graphics_processes()`
+    #[inline]
+    pub fn running_graphics_processes_count(&self) -> Result<u32> {
+        unsafe {
+            // Indicates that we want the count
+            let mut count: c_uint = 0;
+
+            // Passing null doesn't indicate that we want the count. It's just allowed.
+            match nvmlDeviceGetGraphicsRunningProcesses(self.device, &mut count, ptr::null_mut()) {
+                nvmlReturn_enum_NVML_SUCCESS => Ok(0),
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // We know that this will be an error
+                other => nvml_try(other).map(|_| 0),
+            }
+        }
+    }
+
+    /**
+    Gets the NVML index of this `Device`. 
+    
+    Keep in mind that the order in which NVML enumerates devices has no guarantees of
+    consistency between reboots. Also, the NVML index may not correlate with other APIs,
     such as the CUDA device index.
     
     # Errors 
@@ -1297,6 +1657,66 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the max PCIe link generation this `Device` supports, regardless of the
+    system it is in.
+
+    Compare this with `.max_pcie_link_gen()` (which takes the system into account)
+    to determine whether the system is limiting the link.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if PCIe link information is not available
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn gpu_max_pcie_link_gen(&self) -> Result<u32> {
+        unsafe {
+            let mut max_gen: c_uint = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetGpuMaxPcieLinkGeneration(
+                self.device,
+                &mut max_gen
+            ))?;
+
+            Ok(max_gen)
+        }
+    }
+
+    /**
+    Gets the max PCIe link speed possible with this `Device` and system.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if PCIe link information is not available
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn pcie_link_max_speed(&self) -> Result<PcieLinkMaxSpeed> {
+        unsafe {
+            let mut max_speed: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetPcieLinkMaxSpeed(self.device, &mut max_speed))?;
+
+            Ok(max_speed.into())
+        }
+    }
+
     /**
     Gets the requested memory error counter for this `Device`.
     
@@ -1557,99 +1977,186 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Gets the current performance state for this `Device`. 0 == max, 15 == min.
-    
+    Gets the current PCIe link speed (the per-lane transfer rate) in MT/s.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if the device is invalid
-    * `NotSupported`, if this `Device` does not support this feature
+    * `NotSupported`, if PCIe link information is not available
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
-    
+
     # Device Support
 
-    Supports Fermi or newer fully supported devices.
+    Supports Fermi and newer fully supported devices.
     */
     // Checked against local
-    // Tested
     #[inline]
-    pub fn performance_state(&self) -> Result<PerformanceState> {
+    pub fn pcie_link_speed(&self) -> Result<u32> {
         unsafe {
-            let mut state: nvmlPstates_t = mem::zeroed();
-            nvml_try(nvmlDeviceGetPerformanceState(self.device, &mut state))?;
+            let mut speed: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetPcieSpeed(self.device, &mut speed))?;
 
-            Ok(PerformanceState::try_from(state)?)
+            Ok(speed)
         }
     }
 
     /**
-    Gets whether or not persistent mode is enabled for this `Device`.
-    
-    When driver persistence mode is enabled the driver software is not torn down
-    when the last client disconnects. This feature is disabled by default.
-    
+    Gets the type of bus this `Device` is attached to.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if the device is invalid
-    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
-    
-    # Platform Support
-    
-    Only supports Linux.
     */
     // Checked against local
-    // Tested
-    #[cfg(target_os = "linux")]
     #[inline]
-    pub fn is_in_persistent_mode(&self) -> Result<bool> {
+    pub fn bus_type(&self) -> Result<BusType> {
         unsafe {
-            let mut state: nvmlEnableState_t = mem::zeroed();
-            nvml_try(nvmlDeviceGetPersistenceMode(self.device, &mut state))?;
+            let mut bus_type: nvmlBusType_t = mem::zeroed();
+            nvml_try(nvmlDeviceGetBusType(self.device, &mut bus_type))?;
 
-            Ok(bool_from_state(state)?)
+            Ok(bus_type.into())
         }
     }
 
     /**
-    Gets the default power management limit for this `Device`, in milliwatts.
-    
-    This is the limit that this `Device` boots with.
-    
+    Checks whether this `Device`'s PCIe link is running below its max generation or
+    width while the GPU is busy.
+
+    The GPU is considered to be under load if its utilization (as reported by
+    `.utilization_rates()`) is at least `load_threshold` percent. Devices that
+    cannot report their utilization are never considered to be under load. See
+    `PcieLinkHealth` for details.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if the device is invalid
-    * `NotSupported`, if this `Device` does not support this feature
+    * `NotSupported`, if PCIe link information is not available
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
-    
+
     # Device Support
 
-    Supports Kepler or newer fully supported devices.
+    Supports Fermi and newer fully supported devices.
     */
     // Checked against local
-    // Tested
     #[inline]
-    pub fn power_management_limit_default(&self) -> Result<u32> {
-        unsafe {
-            let mut limit: c_uint = mem::zeroed();
-            nvml_try(nvmlDeviceGetPowerManagementDefaultLimit(
-                self.device,
-                &mut limit
-            ))?;
+    pub fn pcie_link_health(&self, load_threshold: u32) -> Result<PcieLinkHealth> {
+        let gpu_utilization = optional(self.utilization_rates())?.map(|rates| rates.gpu);
+        let under_load = match gpu_utilization {
+            Some(utilization) => utilization >= load_threshold,
+            None => false,
+        };
 
-            Ok(limit)
-        }
+        Ok(PcieLinkHealth {
+            current_link_gen: self.current_pcie_link_gen()?,
+            max_link_gen: self.max_pcie_link_gen()?,
+            gpu_max_link_gen: optional(self.gpu_max_pcie_link_gen())?,
+            current_link_width: self.current_pcie_link_width()?,
+            max_link_width: self.max_pcie_link_width()?,
+            gpu_utilization,
+            under_load
+        })
     }
 
     /**
-    Gets the power management limit associated with this `Device`.
+    Gets the current performance state for this `Device`. 0 == max, 15 == min.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    
+    # Device Support
+
+    Supports Fermi or newer fully supported devices.
+    */
+    // Checked against local
+    // Tested
+    #[inline]
+    pub fn performance_state(&self) -> Result<PerformanceState> {
+        unsafe {
+            let mut state: nvmlPstates_t = mem::zeroed();
+            nvml_try(nvmlDeviceGetPerformanceState(self.device, &mut state))?;
+
+            Ok(PerformanceState::from(state))
+        }
+    }
+
+    /**
+    Gets whether or not persistent mode is enabled for this `Device`.
+    
+    When driver persistence mode is enabled the driver software is not torn down
+    when the last client disconnects. This feature is disabled by default.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    
+    # Platform Support
+    
+    Only supports Linux.
+    */
+    // Checked against local
+    // Tested
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn is_in_persistent_mode(&self) -> Result<bool> {
+        unsafe {
+            let mut state: nvmlEnableState_t = mem::zeroed();
+            nvml_try(nvmlDeviceGetPersistenceMode(self.device, &mut state))?;
+
+            Ok(bool_from_state(state)?)
+        }
+    }
+
+    /**
+    Gets the default power management limit for this `Device`, in milliwatts.
+    
+    This is the limit that this `Device` boots with.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    // Checked against local
+    // Tested
+    #[inline]
+    pub fn power_management_limit_default(&self) -> Result<u32> {
+        unsafe {
+            let mut limit: c_uint = mem::zeroed();
+            nvml_try(nvmlDeviceGetPowerManagementDefaultLimit(
+                self.device,
+                &mut limit
+            ))?;
+
+            Ok(limit)
+        }
+    }
+
+    /**
+    Gets the power management limit associated with this `Device`.
     
     The power limit defines the upper boundary for the card's power draw. If the card's
     total power draw reaches this limit, the power management algorithm kicks in.
@@ -1742,7 +2249,7 @@ impl<'nvml> Device<'nvml> {
             let mut state: nvmlPstates_t = mem::zeroed();
             nvml_try(nvmlDeviceGetPowerState(self.device, &mut state))?;
 
-            Ok(PerformanceState::try_from(state)?)
+            Ok(PerformanceState::from(state))
         }
     }
 
@@ -1777,6 +2284,36 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the total energy consumption for this GPU in millijoules (mJ) since the
+    driver was last reloaded.
+
+    If this is not supported by your `Device`, take a look at
+    `high_level::EnergyMeter`, which can integrate power samples instead.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support energy readings
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Volta and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn total_energy_consumption(&self) -> Result<u64> {
+        unsafe {
+            let mut total: c_ulonglong = mem::zeroed();
+            nvml_try(nvmlDeviceGetTotalEnergyConsumption(self.device, &mut total))?;
+
+            Ok(total)
+        }
+    }
+
     /**
     Gets the list of retired pages filtered by `cause`, including pages pending retirement.
 
@@ -1874,6 +2411,78 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets information about rows remapped due to memory errors on this `Device`.
+
+    Row remapping replaces the page retirement mechanism on newer devices; see
+    `.retired_pages()` and `.are_pages_pending_retired()` for older ones.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn remapped_rows(&self) -> Result<RemappedRowsInfo> {
+        unsafe {
+            let mut corr_rows: c_uint = mem::zeroed();
+            let mut unc_rows: c_uint = mem::zeroed();
+            let mut is_pending: c_uint = mem::zeroed();
+            let mut failure_occurred: c_uint = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetRemappedRows(
+                self.device,
+                &mut corr_rows,
+                &mut unc_rows,
+                &mut is_pending,
+                &mut failure_occurred
+            ))?;
+
+            Ok(RemappedRowsInfo {
+                correctable_rows: corr_rows,
+                uncorrectable_rows: unc_rows,
+                is_pending: is_pending != 0,
+                failure_occurred: failure_occurred != 0
+            })
+        }
+    }
+
+    /**
+    Gets the row remapper histogram for this `Device`, which describes how much
+    remapping availability is left in each memory bank.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn row_remapper_histogram(&self) -> Result<RowRemapperHistogram> {
+        unsafe {
+            let mut hist: nvmlRowRemapperHistogramValues_t = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetRowRemapperHistogram(self.device, &mut hist))?;
+
+            Ok(hist.into())
+        }
+    }
+
     /**
     Gets recent samples for this `Device`.
     
@@ -1892,7 +2501,6 @@ impl<'nvml> Device<'nvml> {
     * `NotSupported`, if this query is not supported by this `Device`
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `NotFound`, if sample entries are not found
-    * `UnexpectedVariant`, check that error's docs for more info
     * `Unknown`, on any unexpected error
     
     # Device Support
@@ -1958,7 +2566,7 @@ impl<'nvml> Device<'nvml> {
                 samples.as_mut_ptr()
             ))?;
 
-            let val_type_rust = SampleValueType::try_from(val_type)?;
+            let val_type_rust = SampleValueType::from(val_type);
             Ok(
                 samples
                     .iter()
@@ -1989,6 +2597,246 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Obtain a cursor that streams samples of the given type from this `Device`.
+
+    The cursor remembers the last timestamp it has seen and reuses its buffer
+    between polls; see `SampleCursor` for details.
+    */
+    #[inline]
+    pub fn sample_cursor<'device>(&'device self, sample_type: Sampling) -> SampleCursor<'device, 'nvml> {
+        SampleCursor {
+            device: self,
+            sample_type,
+            last_seen: 0,
+            buffer: Vec::new()
+        }
+    }
+
+    /**
+    Gets power draw samples, in watts, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    This is `.samples(Sampling::Power, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn power_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<f64>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::Power, last_seen_timestamp, milliwatts_to_watts)
+    }
+
+    /**
+    Gets GPU utilization samples, in percent, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    The percent of time during which one or more kernels was executing on the GPU.
+
+    This is `.samples(Sampling::GpuUtilization, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn gpu_utilization_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<u32>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::GpuUtilization, last_seen_timestamp, SampleValue::as_u32)
+    }
+
+    /**
+    Gets memory utilization samples, in percent, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    The percent of time during which global (device) memory was being read or
+    written.
+
+    This is `.samples(Sampling::MemoryUtilization, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn memory_utilization_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<u32>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::MemoryUtilization, last_seen_timestamp, SampleValue::as_u32)
+    }
+
+    /**
+    Gets encoder utilization samples, in percent, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    This is `.samples(Sampling::EncoderUtilization, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn encoder_utilization_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<u32>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::EncoderUtilization, last_seen_timestamp, SampleValue::as_u32)
+    }
+
+    /**
+    Gets decoder utilization samples, in percent, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    This is `.samples(Sampling::DecoderUtilization, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn decoder_utilization_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<u32>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::DecoderUtilization, last_seen_timestamp, SampleValue::as_u32)
+    }
+
+    /**
+    Gets processor clock samples, in MHz, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    This is `.samples(Sampling::ProcessorClock, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn processor_clock_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<u32>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::ProcessorClock, last_seen_timestamp, SampleValue::as_u32)
+    }
+
+    /**
+    Gets memory clock samples, in MHz, for samples with a timestamp later than
+    `last_seen_timestamp`.
+
+    This is `.samples(Sampling::MemoryClock, ..)` with the values converted; see
+    that method for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[inline]
+    pub fn memory_clock_samples<T>(&self, last_seen_timestamp: T) -> Result<Vec<TypedSample<u32>>>
+    where
+        T: Into<Option<u64>>,
+    {
+        self.typed_samples(Sampling::MemoryClock, last_seen_timestamp, SampleValue::as_u32)
+    }
+
+    // Helper for the typed sample methods above.
+    #[inline]
+    fn typed_samples<T, U, F>(
+        &self,
+        sample_type: Sampling,
+        last_seen_timestamp: T,
+        convert: F,
+    ) -> Result<Vec<TypedSample<U>>>
+    where
+        T: Into<Option<u64>>,
+        F: Fn(&SampleValue) -> Option<U>,
+    {
+        let samples = self.samples(sample_type, last_seen_timestamp)?;
+        convert_samples(&samples, convert)
+    }
+
     /**
     Gets the globally unique board serial number associated with this `Device`'s board
     as an alphanumeric string.
@@ -2062,9 +2910,8 @@ impl<'nvml> Device<'nvml> {
     
     Note that multiple reasons can be affecting clocks at once.
 
-    The returned bitmask is created via the `ThrottleReasons::from_bits_truncate`
-    method, meaning that any bits that don't correspond to flags present in this
-    version of the wrapper will be dropped.
+    Any bits that don't correspond to flags present in this version of the
+    wrapper are kept in the returned `Lossless`'s `unknown_bits`.
     
     # Errors
 
@@ -2080,8 +2927,8 @@ impl<'nvml> Device<'nvml> {
     // Checked against local.
     // Tested
     #[inline]
-    pub fn current_throttle_reasons(&self) -> Result<ThrottleReasons> {
-        Ok(ThrottleReasons::from_bits_truncate(self.current_throttle_reasons_raw()?))
+    pub fn current_throttle_reasons(&self) -> Result<Lossless<ThrottleReasons>> {
+        Ok(Lossless::from_bits(self.current_throttle_reasons_raw()?))
     }
 
     /**
@@ -2105,6 +2952,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local.
     // Tested
+    #[deprecated(note = "use `.current_throttle_reasons()` and check `.has_unknown_bits()`")]
     #[inline]
     pub fn current_throttle_reasons_strict(&self) -> Result<ThrottleReasons> {
         let reasons = self.current_throttle_reasons_raw()?;
@@ -2133,9 +2981,8 @@ impl<'nvml> Device<'nvml> {
     
     These reasons can be returned by `.current_throttle_reasons()`.
 
-    The returned bitmask is created via the `ThrottleReasons::from_bits_truncate`
-    method, meaning that any bits that don't correspond to flags present in this
-    version of the wrapper will be dropped.
+    Any bits that don't correspond to flags present in this version of the
+    wrapper are kept in the returned `Lossless`'s `unknown_bits`.
     
     # Errors
 
@@ -2154,8 +3001,8 @@ impl<'nvml> Device<'nvml> {
     // Checked against local
     // Tested
     #[inline]
-    pub fn supported_throttle_reasons(&self) -> Result<ThrottleReasons> {
-        Ok(ThrottleReasons::from_bits_truncate(self.supported_throttle_reasons_raw()?))
+    pub fn supported_throttle_reasons(&self) -> Result<Lossless<ThrottleReasons>> {
+        Ok(Lossless::from_bits(self.supported_throttle_reasons_raw()?))
     }
 
     /**
@@ -2182,6 +3029,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local
     // Tested
+    #[deprecated(note = "use `.supported_throttle_reasons()` and check `.has_unknown_bits()`")]
     #[inline]
     pub fn supported_throttle_reasons_strict(&self) -> Result<ThrottleReasons> {
         let reasons = self.supported_throttle_reasons_raw()?;
@@ -2298,47 +3146,118 @@ impl<'nvml> Device<'nvml> {
         let mut count = size as c_uint;
 
         unsafe {
-            match nvmlDeviceGetSupportedMemoryClocks(
+            match nvmlDeviceGetSupportedMemoryClocks(
+                self.device,
+                &mut count,
+                items.as_mut_ptr()
+            ) {
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => 
+                    // `count` is now the size that is required. Return it in the error.
+                    bail!(ErrorKind::InsufficientSize(Some(count as usize))),
+                value => nvml_try(value)?,
+            }
+        }
+
+        items.truncate(count as usize);
+        Ok(items)
+    }
+
+    /**
+    Gets the current temperature readings for the given sensor, in °C.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid or `sensor` is invalid (shouldn't occur?)
+    * `NotSupported`, if this `Device` does not have the specified sensor
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested
+    #[inline]
+    pub fn temperature(&self, sensor: TemperatureSensor) -> Result<u32> {
+        unsafe {
+            let mut temp: c_uint = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetTemperature(
+                self.device,
+                sensor.as_c(),
+                &mut temp
+            ))?;
+
+            Ok(temp)
+        }
+    }
+
+    /**
+    Gets the settings and current readings of every thermal sensor on this `Device`.
+
+    `.temperature()` only reports the GPU core temperature; this also covers
+    sensors for memory, the board, and so on (see `ThermalTarget`).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    #[inline]
+    pub fn thermal_settings(&self) -> Result<Vec<ThermalSensor>> {
+        unsafe {
+            let mut settings: nvmlGpuThermalSettings_t = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetThermalSettings(
                 self.device,
-                &mut count,
-                items.as_mut_ptr()
-            ) {
-                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => 
-                    // `count` is now the size that is required. Return it in the error.
-                    bail!(ErrorKind::InsufficientSize(Some(count as usize))),
-                value => nvml_try(value)?,
-            }
-        }
+                nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL as c_uint,
+                &mut settings
+            ))?;
 
-        items.truncate(count as usize);
-        Ok(items)
+            let count = cmp::min(settings.count as usize, settings.sensor.len());
+            Ok(settings.sensor[..count].iter().map(|s| (*s).into()).collect())
+        }
     }
 
     /**
-    Gets the current temperature readings for the given sensor, in °C.
-    
+    Gets the current memory (HBM) temperature for this `Device`, in °C.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the device is invalid or `sensor` is invalid (shouldn't occur?)
-    * `NotSupported`, if this `Device` does not have the specified sensor
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if this `Device` does not report memory temperature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports devices with HBM memory (Volta and newer datacenter products).
     */
     // Checked against local
-    // Tested
     #[inline]
-    pub fn temperature(&self, sensor: TemperatureSensor) -> Result<u32> {
+    pub fn memory_temperature(&self) -> Result<u32> {
+        match self.field_value(NVML_FI_DEV_MEMORY_TEMP)? {
+            SampleValue::Unknown(value_type, _) => bail!(ErrorKind::UnexpectedVariant(value_type)),
+            value => Ok(value.as_u32().unwrap_or(0)),
+        }
+    }
+
+    // Helper for methods backed by field value queries.
+    #[inline]
+    fn field_value(&self, field_id: c_uint) -> Result<SampleValue> {
         unsafe {
-            let mut temp: c_uint = mem::zeroed();
+            let mut value: nvmlFieldValue_t = mem::zeroed();
+            value.fieldId = field_id;
 
-            nvml_try(nvmlDeviceGetTemperature(
-                self.device,
-                sensor.as_c(),
-                &mut temp
-            ))?;
+            nvml_try(nvmlDeviceGetFieldValues(self.device, 1, &mut value))?;
+            // Each field carries its own return code
+            nvml_try(value.nvmlReturn)?;
 
-            Ok(temp)
+            let value_type = SampleValueType::from(value.valueType);
+            Ok(SampleValue::from_tag_and_union(&value_type, value.value))
         }
     }
 
@@ -2381,7 +3300,6 @@ impl<'nvml> Device<'nvml> {
 
     * `InvalidArg`, if either `Device` is invalid
     * `NotSupported`, if this `Device` or the OS does not support this feature
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, an error has occurred in the underlying topology discovery
     
     # Platform Support
@@ -2402,7 +3320,7 @@ impl<'nvml> Device<'nvml> {
                 &mut level
             ))?;
 
-            Ok(TopologyLevel::try_from(level)?)
+            Ok(TopologyLevel::from(level))
         }
     }
 
@@ -2463,6 +3381,33 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the status of the given peer-to-peer capability between this `Device`
+    and `other_device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if either `Device` is invalid
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    #[inline]
+    pub fn p2p_status(&self, other_device: &Device, index: P2pCapabilitiesIndex) -> Result<P2pStatus> {
+        unsafe {
+            let mut status: nvmlGpuP2PStatus_t = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetP2PStatus(
+                self.device,
+                other_device.device,
+                index.as_c(),
+                &mut status
+            ))?;
+
+            Ok(P2pStatus::from(status))
+        }
+    }
+
     /**
     Gets the total ECC error counts for this `Device`.
     
@@ -3087,133 +4032,354 @@ impl<'nvml> Device<'nvml> {
     // Checked against local
     // Tested (no-run)
     #[inline]
-    pub fn set_accounting(&mut self, enabled: bool) -> Result<()> {
-        unsafe {
-            nvml_try(nvmlDeviceSetAccountingMode(
-                self.device,
-                state_from_bool(enabled)
-            ))
+    pub fn set_accounting(&mut self, enabled: bool) -> Result<()> {
+        unsafe {
+            nvml_try(nvmlDeviceSetAccountingMode(
+                self.device,
+                state_from_bool(enabled)
+            ))
+        }
+    }
+
+    // Device commands starting here
+
+    /**
+    Clears the ECC error and other memory error counts for this `Device`.
+    
+    Sets all of the specified ECC counters to 0, including both detailed and total counts.
+    This operation takes effect immediately.
+    
+    Requires root/admin permissions and ECC mode to be enabled.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or `counter_type` is invalid (shouldn't occur?)
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    
+    # Device Support
+
+    Supports Kepler and newer fully supported devices. Only applicable to devices with
+    ECC. Requires `InfoRom::ECC` version 2.0 or higher to clear aggregate
+    location-based ECC counts. Requires `InfoRom::ECC` version 1.0 or higher to
+    clear all other ECC counts.
+    */
+    // Checked against local
+    // Tested (no-run)
+    #[inline]
+    pub fn clear_ecc_error_counts(&mut self, counter_type: EccCounter) -> Result<()> {
+        unsafe {
+            nvml_try(nvmlDeviceClearEccErrorCounts(
+                self.device,
+                counter_type.as_c()
+            ))
+        }
+    }
+
+    /**
+    Changes the root/admin restrictions on certain APIs.
+    
+    This method can be used by a root/admin user to give non root/admin users access
+    to certain otherwise-restricted APIs. The new setting lasts for the lifetime of
+    the NVIDIA driver; it is not persistent. See `.is_api_restricted()` to query
+    current settings.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or `api_type` is invalid (shouldn't occur?)
+    * `NotSupported`, if this `Device` does not support changing API restrictions or
+    this `Device` does not support the feature that API restrictions are being set for
+    (e.g. enabling/disabling auto boosted clocks is not supported by this `Device`).
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Checked against local
+    // Tested (no-run)
+    #[inline]
+    pub fn set_api_restricted(&mut self, api_type: Api, restricted: bool) -> Result<()> {
+        unsafe {
+            nvml_try(nvmlDeviceSetAPIRestriction(
+                self.device,
+                api_type.as_c(),
+                state_from_bool(restricted)
+            ))
+        }
+    }
+
+    /**
+    Sets clocks that applications will lock to.
+    
+    Sets the clocks that compute and graphics applications will be running at. e.g.
+    CUDA driver requests these clocks during context creation which means this
+    property defines clocks at which CUDA applications will be running unless some
+    overspec event occurs (e.g. over power, over thermal or external HW brake).
+    
+    Can be used as a setting to request constant performance. Requires root/admin
+    permissions.
+    
+    On Pascal and newer hardware, this will automatically disable automatic boosting
+    of clocks. On K80 and newer Kepler and Maxwell GPUs, users desiring fixed performance
+    should also call `.set_auto_boosted_clocks(false)` to prevent clocks from automatically
+    boosting above the clock value being set here.
+    
+    Note that after a system reboot or driver reload applications clocks go back
+    to their default value.
+    
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or the clocks are not a valid combo
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    
+    # Device Support
+
+    Supports Kepler and newer non-GeForce fully supported devices and Maxwell or newer
+    GeForce devices.
+    */
+    // Checked against local
+    // Tested (no-run)
+    #[inline]
+    pub fn set_applications_clocks(&mut self, mem_clock: u32, graphics_clock: u32) -> Result<()> {
+        unsafe {
+            nvml_try(nvmlDeviceSetApplicationsClocks(
+                self.device,
+                mem_clock,
+                graphics_clock
+            ))
+        }
+    }
+
+    /**
+    Gets the clock offset (in MHz) applied to the given clock domain in the given
+    performance state, along with the range of offsets that are allowed.
+
+    Use `Clock::Graphics` for the GPC (core) clock and `Clock::Memory` for the
+    memory clock. Offsets shift the device's voltage/frequency curve; negative
+    offsets underclock and positive offsets overclock.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or the clock type or performance
+      state is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn clock_offset(&self, clock_type: Clock, power_state: PerformanceState) -> Result<ClockOffset> {
+        unsafe {
+            let mut offset: nvmlClockOffset_t = mem::zeroed();
+            offset.version = nvmlClockOffset_v1;
+            offset.type_ = clock_type.as_c();
+            offset.pstate = power_state.as_c();
+
+            nvml_try(nvmlDeviceGetClockOffsets(self.device, &mut offset))?;
+
+            Ok(ClockOffset::from(offset))
+        }
+    }
+
+    /**
+    Sets the clock offset (in MHz) applied to the given clock domain in the given
+    performance state.
+
+    The offset is checked against the range reported by `.clock_offset()` before it
+    is applied. Use `Clock::Graphics` for the GPC (core) clock and `Clock::Memory`
+    for the memory clock.
+
+    Requires root/admin permissions. Offsets go back to zero after a driver
+    reload.
+
+    # Errors
+
+    * `ClockOffsetOutOfRange`, if `offset_mhz` is outside of the allowed range
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or the clock type or performance
+      state is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn set_clock_offset(
+        &mut self,
+        clock_type: Clock,
+        power_state: PerformanceState,
+        offset_mhz: i32
+    ) -> Result<()> {
+        let current = self.clock_offset(clock_type.clone(), power_state.clone())?;
+
+        if offset_mhz < current.min_clock_offset_mhz || offset_mhz > current.max_clock_offset_mhz {
+            bail!(ErrorKind::ClockOffsetOutOfRange(
+                offset_mhz,
+                current.min_clock_offset_mhz,
+                current.max_clock_offset_mhz
+            ));
+        }
+
+        unsafe {
+            let mut offset: nvmlClockOffset_t = mem::zeroed();
+            offset.version = nvmlClockOffset_v1;
+            offset.type_ = clock_type.as_c();
+            offset.pstate = power_state.as_c();
+            offset.clockOffsetMHz = offset_mhz;
+
+            nvml_try(nvmlDeviceSetClockOffsets(self.device, &mut offset))
+        }
+    }
+
+    /**
+    Locks the GPU clock to the given range (in MHz) until the clocks are reset.
+
+    Unlike applications clocks, locked clocks apply to every workload, which makes
+    them suitable for getting deterministic clocks when benchmarking. The clock may
+    still drop below `min_clock` in the event of an overspec event (e.g. over power,
+    over thermal or external HW brake).
+
+    The range is checked against the lowest clock reported by
+    `.supported_graphics_clocks()` (across every supported memory clock) and
+    `.max_clock_info(Clock::Graphics)` before NVML is called.
+
+    Requires root/admin permissions. Locked clocks go back to their default after
+    a driver reload; call `.reset_gpu_locked_clocks()` to reset them sooner.
+
+    # Errors
+
+    * `ClockRangeOutOfBounds`, if `min_clock` is greater than `max_clock` or either
+      lies outside of the supported range
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Volta and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn set_gpu_locked_clocks(&mut self, min_clock: u32, max_clock: u32) -> Result<()> {
+        let mut supported_min = None;
+        for mem_clock in self.supported_memory_clocks()? {
+            for clock in self.supported_graphics_clocks(mem_clock)? {
+                supported_min = Some(supported_min.map_or(clock, |m| cmp::min(m, clock)));
+            }
         }
-    }
 
-    // Device commands starting here
+        let supported_max = self.max_clock_info(Clock::Graphics)?;
+        check_clock_range(min_clock, max_clock, supported_min.unwrap_or(0), supported_max)?;
+
+        unsafe { nvml_try(nvmlDeviceSetGpuLockedClocks(self.device, min_clock, max_clock)) }
+    }
 
     /**
-    Clears the ECC error and other memory error counts for this `Device`.
-    
-    Sets all of the specified ECC counters to 0, including both detailed and total counts.
-    This operation takes effect immediately.
-    
-    Requires root/admin permissions and ECC mode to be enabled.
-    
+    Resets the GPU clock to its default behavior, undoing `.set_gpu_locked_clocks()`.
+
+    Requires root/admin permissions.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the `Device` is invalid or `counter_type` is invalid (shouldn't occur?)
+    * `InvalidArg`, if the `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `NoPermission`, if the user doesn't have permission to perform this operation
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
-    
+
     # Device Support
 
-    Supports Kepler and newer fully supported devices. Only applicable to devices with
-    ECC. Requires `InfoRom::ECC` version 2.0 or higher to clear aggregate
-    location-based ECC counts. Requires `InfoRom::ECC` version 1.0 or higher to
-    clear all other ECC counts.
+    Supports Volta and newer fully supported devices.
     */
     // Checked against local
-    // Tested (no-run)
     #[inline]
-    pub fn clear_ecc_error_counts(&mut self, counter_type: EccCounter) -> Result<()> {
-        unsafe {
-            nvml_try(nvmlDeviceClearEccErrorCounts(
-                self.device,
-                counter_type.as_c()
-            ))
-        }
+    pub fn reset_gpu_locked_clocks(&mut self) -> Result<()> {
+        unsafe { nvml_try(nvmlDeviceResetGpuLockedClocks(self.device)) }
     }
 
     /**
-    Changes the root/admin restrictions on certain APIs.
-    
-    This method can be used by a root/admin user to give non root/admin users access
-    to certain otherwise-restricted APIs. The new setting lasts for the lifetime of
-    the NVIDIA driver; it is not persistent. See `.is_api_restricted()` to query
-    current settings.
-    
+    Locks the memory clock to the given range (in MHz) until the clocks are reset.
+
+    The range is checked against the lowest clock reported by
+    `.supported_memory_clocks()` and `.max_clock_info(Clock::Memory)` before NVML
+    is called.
+
+    Requires root/admin permissions. Locked clocks go back to their default after
+    a driver reload; call `.reset_memory_locked_clocks()` to reset them sooner.
+
     # Errors
 
+    * `ClockRangeOutOfBounds`, if `min_clock` is greater than `max_clock` or either
+      lies outside of the supported range
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the `Device` is invalid or `api_type` is invalid (shouldn't occur?)
-    * `NotSupported`, if this `Device` does not support changing API restrictions or
-    this `Device` does not support the feature that API restrictions are being set for
-    (e.g. enabling/disabling auto boosted clocks is not supported by this `Device`).
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
     * `NoPermission`, if the user doesn't have permission to perform this operation
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
-    
+
     # Device Support
 
-    Supports Kepler and newer fully supported devices.
+    Supports Ampere and newer fully supported devices.
     */
     // Checked against local
-    // Tested (no-run)
     #[inline]
-    pub fn set_api_restricted(&mut self, api_type: Api, restricted: bool) -> Result<()> {
-        unsafe {
-            nvml_try(nvmlDeviceSetAPIRestriction(
-                self.device,
-                api_type.as_c(),
-                state_from_bool(restricted)
-            ))
-        }
+    pub fn set_memory_locked_clocks(&mut self, min_clock: u32, max_clock: u32) -> Result<()> {
+        let supported_min = self.supported_memory_clocks()?.into_iter().min();
+        let supported_max = self.max_clock_info(Clock::Memory)?;
+        check_clock_range(min_clock, max_clock, supported_min.unwrap_or(0), supported_max)?;
+
+        unsafe { nvml_try(nvmlDeviceSetMemoryLockedClocks(self.device, min_clock, max_clock)) }
     }
 
     /**
-    Sets clocks that applications will lock to.
-    
-    Sets the clocks that compute and graphics applications will be running at. e.g.
-    CUDA driver requests these clocks during context creation which means this
-    property defines clocks at which CUDA applications will be running unless some
-    overspec event occurs (e.g. over power, over thermal or external HW brake).
-    
-    Can be used as a setting to request constant performance. Requires root/admin
-    permissions.
-    
-    On Pascal and newer hardware, this will automatically disable automatic boosting
-    of clocks. On K80 and newer Kepler and Maxwell GPUs, users desiring fixed performance
-    should also call `.set_auto_boosted_clocks(false)` to prevent clocks from automatically
-    boosting above the clock value being set here.
-    
-    Note that after a system reboot or driver reload applications clocks go back
-    to their default value.
-    
+    Resets the memory clock to its default behavior, undoing
+    `.set_memory_locked_clocks()`.
+
+    Requires root/admin permissions.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the `Device` is invalid or the clocks are not a valid combo
+    * `InvalidArg`, if the `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `NoPermission`, if the user doesn't have permission to perform this operation
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
-    
+
     # Device Support
 
-    Supports Kepler and newer non-GeForce fully supported devices and Maxwell or newer
-    GeForce devices.
+    Supports Ampere and newer fully supported devices.
     */
     // Checked against local
-    // Tested (no-run)
     #[inline]
-    pub fn set_applications_clocks(&mut self, mem_clock: u32, graphics_clock: u32) -> Result<()> {
-        unsafe {
-            nvml_try(nvmlDeviceSetApplicationsClocks(
-                self.device,
-                mem_clock,
-                graphics_clock
-            ))
-        }
+    pub fn reset_memory_locked_clocks(&mut self) -> Result<()> {
+        unsafe { nvml_try(nvmlDeviceResetMemoryLockedClocks(self.device)) }
     }
 
     /**
@@ -3442,6 +4608,133 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(nvmlDeviceSetPowerManagementLimit(self.device, limit)) }
     }
 
+    /**
+    Sets the control policy of the specified fan.
+
+    Setting the policy to `FanControlPolicy::Manual` is done implicitly by
+    `.set_fan_speed()`; set it back to `FanControlPolicy::TemperatureContinuousSw`
+    (or call `.set_default_fan_speed()`) to hand control back to the driver.
+
+    Requires root/admin permissions.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid or `fan_idx` is invalid
+    * `NotSupported`, if this `Device` does not have a fan or does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported discrete devices with fans.
+    */
+    // Checked against local
+    #[inline]
+    pub fn set_fan_control_policy(&mut self, fan_idx: u32, policy: FanControlPolicy) -> Result<()> {
+        unsafe {
+            nvml_try(nvmlDeviceSetFanControlPolicy(
+                self.device,
+                fan_idx,
+                policy.as_c()
+            ))
+        }
+    }
+
+    /**
+    Sets the speed of the specified fan as a percentage of the maximum fan speed.
+
+    This puts the fan under manual control. **The driver will no longer adjust the
+    fan speed in response to temperature changes**, so make sure to call
+    `.set_default_fan_speed()` when you are done. See
+    `high_level::FanGuard` for a wrapper that refuses to set dangerously low
+    speeds while the GPU is hot.
+
+    Requires root/admin permissions. See `.min_max_fan_speed()` to check the allowed
+    range of values.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid, `fan_idx` is invalid, or `speed` is
+      out of range
+    * `NotSupported`, if this `Device` does not have a fan or does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported discrete devices with fans.
+    */
+    // Checked against local
+    #[inline]
+    pub fn set_fan_speed(&mut self, fan_idx: u32, speed: u32) -> Result<()> {
+        unsafe { nvml_try(nvmlDeviceSetFanSpeed_v2(self.device, fan_idx, speed)) }
+    }
+
+    /**
+    Restores the specified fan to its default, driver-controlled speed.
+
+    Requires root/admin permissions.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid or `fan_idx` is invalid
+    * `NotSupported`, if this `Device` does not have a fan or does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported discrete devices with fans.
+    */
+    // Checked against local
+    #[inline]
+    pub fn set_default_fan_speed(&mut self, fan_idx: u32) -> Result<()> {
+        unsafe { nvml_try(nvmlDeviceSetDefaultFanSpeed_v2(self.device, fan_idx)) }
+    }
+
+    /**
+    Sets the temperature threshold for this `Device` and the specified
+    `threshold_type`, in °C.
+
+    Only `TemperatureThreshold::AcousticCurrent` can be set; the allowed range is
+    reported by the `AcousticMin` and `AcousticMax` thresholds.
+
+    Requires root/admin permissions.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid, `threshold_type` cannot be set,
+      or `temp` is out of range
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn set_temperature_threshold(&mut self, threshold_type: TemperatureThreshold, temp: i32) -> Result<()> {
+        let mut temp = temp;
+
+        unsafe {
+            nvml_try(nvmlDeviceSetTemperatureThreshold(
+                self.device,
+                threshold_type.as_c(),
+                &mut temp
+            ))
+        }
+    }
+
     // Event handling methods
 
     /**
@@ -3552,9 +4845,8 @@ impl<'nvml> Device<'nvml> {
     /**
     Gets the `EventTypes` that this `Device` supports.
 
-    The returned bitmask is created via the `EventTypes::from_bits_truncate`
-    method, meaning that any bits that don't correspond to flags present in this
-    version of the wrapper will be dropped.
+    Any bits that don't correspond to flags present in this version of the
+    wrapper are kept in the returned `Lossless`'s `unknown_bits`.
     
     # Errors
 
@@ -3583,7 +4875,7 @@ impl<'nvml> Device<'nvml> {
     # let device = nvml.device_by_index(0)?;
     use nvml_wrapper::bitmasks::event::EventTypes;
 
-    let supported = device.supported_event_types()?;
+    let supported = device.supported_event_types()?.flags;
 
     if supported.contains(EventTypes::CLOCK_CHANGE) {
         println!("The `CLOCK_CHANGE` event is supported.");
@@ -3600,8 +4892,8 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[cfg(target_os = "linux")]
     #[inline]
-    pub fn supported_event_types(&self) -> Result<EventTypes> {
-        Ok(EventTypes::from_bits_truncate(self.supported_event_types_raw()?))
+    pub fn supported_event_types(&self) -> Result<Lossless<EventTypes>> {
+        Ok(Lossless::from_bits(self.supported_event_types_raw()?))
     }
 
     /**
@@ -3626,6 +4918,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Tested
     #[cfg(target_os = "linux")]
+    #[deprecated(note = "use `.supported_event_types()` and check `.has_unknown_bits()`")]
     #[inline]
     pub fn supported_event_types_strict(&self) -> Result<EventTypes> {
         let ev_types = self.supported_event_types_raw()?;
@@ -3929,6 +5222,47 @@ impl<'nvml> Device<'nvml> {
 
     // NvLink
 
+    /**
+    Obtain structs representing every active NvLink on this `Device`.
+
+    Every link index up to the NVML maximum is checked with `NvLink.is_active()`;
+    inactive links and links that do not support the query are skipped. Returns
+    an empty `Vec` if this `Device` does not have any NvLinks.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[inline]
+    pub fn nvlinks<'device>(&'device self) -> Result<Vec<NvLink<'device, 'nvml>>> {
+        let mut links = Vec::new();
+
+        for link in 0..NVML_NVLINK_MAX_LINKS {
+            let link = NvLink {
+                device: self,
+                link
+            };
+
+            match link.is_active() {
+                Ok(true) => links.push(link),
+                // A link that cannot report its state does not hide the ones
+                // after it
+                Ok(false) | Err(Error(ErrorKind::NotSupported, _)) => {},
+                // The device has fewer links than the maximum
+                Err(Error(ErrorKind::InvalidArg, _)) => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(links)
+    }
+
     /**
     Obtain a struct that represents an NvLink.
 
@@ -3943,6 +5277,16 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Obtain a view of this `Device` whose methods return strongly typed units.
+
+    See the `units` module for details.
+    */
+    #[inline]
+    pub fn typed<'device>(&'device self) -> TypedDevice<'device, 'nvml> {
+        TypedDevice { device: self }
+    }
+
     /// Consume the struct and obtain the raw device handle that it contains.
     #[inline]
     pub fn into_raw(self) -> nvmlDevice_t {
@@ -3969,6 +5313,40 @@ impl<'nvml> Device<'nvml> {
     }
 }
 
+// Helper for the typed sample methods.
+fn convert_samples<U, F>(samples: &[Sample], convert: F) -> Result<Vec<TypedSample<U>>>
+where
+    F: Fn(&SampleValue) -> Option<U>,
+{
+    samples
+        .iter()
+        .map(|s| match (convert(&s.value), &s.value) {
+            (Some(value), _) => Ok(TypedSample {
+                timestamp: s.timestamp,
+                value
+            }),
+            (None, &SampleValue::Unknown(value_type, _)) => {
+                bail!(ErrorKind::UnexpectedVariant(value_type))
+            },
+            (None, _) => bail!(ErrorKind::Unknown),
+        })
+        .collect()
+}
+
+// Helper for `.power_samples()`. NVML reports power in mW.
+fn milliwatts_to_watts(value: &SampleValue) -> Option<f64> {
+    value.as_f64().map(|milliwatts| milliwatts / 1000.0)
+}
+
+// Helper for the locked clocks methods. Values are in MHz.
+fn check_clock_range(min: u32, max: u32, supported_min: u32, supported_max: u32) -> Result<()> {
+    if min > max || min < supported_min || max > supported_max {
+        bail!(ErrorKind::ClockRangeOutOfBounds(min, max, supported_min, supported_max));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 #[deny(unused_mut)]
 mod test {
@@ -4044,6 +5422,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.bar1_memory_info())
     }
 
+    #[test]
+    fn architecture() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.architecture())
+    }
+
+    #[test]
+    fn cuda_compute_capability() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.cuda_compute_capability())
+    }
+
     #[test]
     fn board_id() {
         let nvml = nvml();
@@ -4121,16 +5511,49 @@ mod test {
     }
 
     #[test]
-    fn running_compute_processes() {
+    fn running_compute_processes() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.running_compute_processes())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cpu_affinity() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.cpu_affinity(64))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cpu_affinity_set() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.cpu_affinity_set(64))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cpu_affinity_within_scope() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.cpu_affinity_within_scope(64, AffinityScope::Node)?;
+            device.cpu_affinity_within_scope(64, AffinityScope::Socket)
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn memory_affinity() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.running_compute_processes())
+        test_with_device(3, &nvml, |device| {
+            device.memory_affinity(64, AffinityScope::Node)?;
+            device.memory_affinity(64, AffinityScope::Socket)
+        })
     }
 
-    #[cfg(target_os = "linux")]
     #[test]
-    fn cpu_affinity() {
+    fn numa_node() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.cpu_affinity(64))
+        test_with_device(3, &nvml, |device| device.numa_node())
     }
 
     #[test]
@@ -4219,6 +5642,36 @@ mod test {
         test_with_device(3, &nvml, |device| device.fan_speed())
     }
 
+    #[test]
+    fn fan_speed_for() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.fan_speed_for(0))
+    }
+
+    #[test]
+    fn target_fan_speed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.target_fan_speed(0))
+    }
+
+    #[test]
+    fn num_fans() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.num_fans())
+    }
+
+    #[test]
+    fn min_max_fan_speed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.min_max_fan_speed())
+    }
+
+    #[test]
+    fn fan_control_policy() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.fan_control_policy(0))
+    }
+
     // My machine does not support this call
     #[cfg(not(feature = "test-local"))]
     #[test]
@@ -4298,6 +5751,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.max_pcie_link_width())
     }
 
+    #[test]
+    fn gpu_max_pcie_link_gen() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_max_pcie_link_gen())
+    }
+
+    #[test]
+    fn pcie_link_max_speed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.pcie_link_max_speed())
+    }
+
     // My machine does not support this call
     #[cfg(not(feature = "test-local"))]
     #[test]
@@ -4358,6 +5823,24 @@ mod test {
         })
     }
 
+    #[test]
+    fn pcie_link_speed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.pcie_link_speed())
+    }
+
+    #[test]
+    fn bus_type() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.bus_type())
+    }
+
+    #[test]
+    fn pcie_link_health() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.pcie_link_health(10))
+    }
+
     #[test]
     fn performance_state() {
         let nvml = nvml();
@@ -4414,6 +5897,14 @@ mod test {
         test_with_device(3, &nvml, |device| device.power_usage())
     }
 
+    // My machine does not support this call
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn total_energy_consumption() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.total_energy_consumption())
+    }
+
     // My machine does not support this call
     #[cfg(not(feature = "test-local"))]
     #[test]
@@ -4437,6 +5928,22 @@ mod test {
         test_with_device(3, &nvml, |device| device.are_pages_pending_retired())
     }
 
+    // My machine does not support this call
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn remapped_rows() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.remapped_rows())
+    }
+
+    // My machine does not support this call
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn row_remapper_histogram() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.row_remapper_histogram())
+    }
+
     #[test]
     fn samples() {
         let nvml = nvml();
@@ -4446,6 +5953,69 @@ mod test {
         })
     }
 
+    #[test]
+    fn power_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.power_samples(None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn gpu_utilization_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.gpu_utilization_samples(None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn memory_utilization_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.memory_utilization_samples(None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn encoder_utilization_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.encoder_utilization_samples(None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn decoder_utilization_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.decoder_utilization_samples(None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn processor_clock_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.processor_clock_samples(None)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn memory_clock_samples() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.memory_clock_samples(None)?;
+            Ok(())
+        })
+    }
+
     // My machine does not support this call
     #[cfg(not(feature = "test-local"))]
     #[test]
@@ -4471,6 +6041,8 @@ mod test {
     #[test]
     fn current_throttle_reasons_strict() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| device.current_throttle_reasons_strict())
     }
 
@@ -4483,6 +6055,8 @@ mod test {
     #[test]
     fn supported_throttle_reasons_strict() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| device.supported_throttle_reasons_strict())
     }
 
@@ -4558,6 +6132,41 @@ mod test {
         })
     }
 
+    // My machine does not support this call
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn temperature_threshold_newer() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .temperature_threshold(TemperatureThreshold::GpuMax)
+                .chain_err(|| "gpumax")?;
+            device
+                .temperature_threshold(TemperatureThreshold::AcousticMin)
+                .chain_err(|| "acousticmin")?;
+            device
+                .temperature_threshold(TemperatureThreshold::AcousticCurrent)
+                .chain_err(|| "acousticcurrent")?;
+            device
+                .temperature_threshold(TemperatureThreshold::AcousticMax)
+                .chain_err(|| "acousticmax")
+        })
+    }
+
+    #[test]
+    fn thermal_settings() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.thermal_settings())
+    }
+
+    // My machine does not support this call
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn memory_temperature() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.memory_temperature())
+    }
+
     // I do not have 2 devices
     #[cfg(not(feature = "test-local"))]
     #[cfg(target_os = "linux")]
@@ -4578,6 +6187,17 @@ mod test {
         test(3, || device.topology_nearest_gpus(TopologyLevel::System))
     }
 
+    // I do not have 2 devices
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn p2p_status() {
+        let nvml = nvml();
+        let device1 = device(&nvml);
+        let device2 = nvml.device_by_index(1).expect("device");
+
+        test(3, || device1.p2p_status(&device2, P2pCapabilitiesIndex::Read))
+    }
+
     // My machine does not support this call
     #[cfg(not(feature = "test-local"))]
     #[test]
@@ -4748,6 +6368,129 @@ mod test {
         device.set_applications_clocks(32, 32).expect("set to true")
     }
 
+    // My machine does not support this call
+    #[cfg(not(feature = "test-local"))]
+    #[test]
+    fn clock_offset() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .clock_offset(Clock::Graphics, PerformanceState::Zero)
+                .chain_err(|| "graphics")?;
+            device
+                .clock_offset(Clock::Memory, PerformanceState::Zero)
+                .chain_err(|| "memory")
+        })
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_clock_offset() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_clock_offset(Clock::Graphics, PerformanceState::Zero, -100)
+            .expect("set to -100 MHz")
+    }
+
+    #[test]
+    fn check_clock_range() {
+        use super::check_clock_range;
+
+        check_clock_range(300, 1500, 300, 1500).expect("full range");
+        check_clock_range(1000, 1000, 300, 1500).expect("single clock");
+
+        match check_clock_range(1500, 300, 300, 1500) {
+            Err(Error(ErrorKind::ClockRangeOutOfBounds(1500, 300, 300, 1500), _)) => {},
+            other => panic!("min > max was accepted: {:?}", other),
+        }
+
+        assert!(check_clock_range(200, 1500, 300, 1500).is_err());
+        assert!(check_clock_range(300, 1600, 300, 1500).is_err());
+    }
+
+    #[test]
+    fn convert_samples() {
+        use super::{convert_samples, milliwatts_to_watts};
+        use enums::device::SampleValue;
+        use struct_wrappers::device::Sample;
+
+        let samples = vec![
+            Sample { timestamp: 1, value: SampleValue::U32(150_000) },
+            Sample { timestamp: 2, value: SampleValue::U64(2_500) },
+            Sample { timestamp: 3, value: SampleValue::F64(500.0) },
+        ];
+
+        let watts = convert_samples(&samples, milliwatts_to_watts).unwrap();
+        assert_eq!(watts.iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(watts.iter().map(|s| s.value).collect::<Vec<_>>(), vec![150.0, 2.5, 0.5]);
+
+        let values = convert_samples(&samples, SampleValue::as_u32).unwrap();
+        assert_eq!(values.iter().map(|s| s.value).collect::<Vec<_>>(), vec![150_000, 2_500, 500]);
+    }
+
+    #[test]
+    fn convert_signed_and_unknown_samples() {
+        use super::convert_samples;
+        use enums::device::SampleValue;
+        use error::{Error, ErrorKind};
+        use struct_wrappers::device::Sample;
+
+        let signed = vec![
+            Sample { timestamp: 1, value: SampleValue::I32(-5) },
+            Sample { timestamp: 2, value: SampleValue::I64(42) },
+        ];
+
+        let values = convert_samples(&signed, SampleValue::as_u32).unwrap();
+        assert_eq!(values.iter().map(|s| s.value).collect::<Vec<_>>(), vec![0, 42]);
+
+        let unknown = vec![Sample { timestamp: 1, value: SampleValue::Unknown(99, [0; 8]) }];
+
+        match convert_samples(&unknown, SampleValue::as_f64) {
+            Err(Error(ErrorKind::UnexpectedVariant(99), _)) => {},
+            other => panic!("expected UnexpectedVariant(99), got {:?}", other),
+        }
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_gpu_locked_clocks() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+        let max = device.max_clock_info(Clock::Graphics).expect("max graphics clock");
+
+        device.set_gpu_locked_clocks(max, max).expect("locked to max")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn reset_gpu_locked_clocks() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.reset_gpu_locked_clocks().expect("reset")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_memory_locked_clocks() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+        let max = device.max_clock_info(Clock::Memory).expect("max memory clock");
+
+        device.set_memory_locked_clocks(max, max).expect("locked to max")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn reset_memory_locked_clocks() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.reset_memory_locked_clocks().expect("reset")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_compute_mode() {
@@ -4804,6 +6547,46 @@ mod test {
         device.set_power_management_limit(250000).expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_temperature_threshold() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_temperature_threshold(TemperatureThreshold::AcousticCurrent, 80)
+            .expect("set to 80 C")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_fan_control_policy() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_fan_control_policy(0, FanControlPolicy::TemperatureContinuousSw)
+            .expect("set to temperature controlled")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_fan_speed() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.set_fan_speed(0, 80).expect("set to 80%")
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_default_fan_speed() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.set_default_fan_speed(0).expect("restored default")
+    }
+
     #[cfg(target_os = "linux")]
     #[allow(unused_variables)]
     #[test]
@@ -4833,6 +6616,8 @@ mod test {
     #[test]
     fn supported_event_types_strict() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| device.supported_event_types_strict())
     }
 
@@ -4842,4 +6627,14 @@ mod test {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| device.is_drain_enabled(None))
     }
+
+    #[test]
+    fn nvlinks() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .nvlinks()
+                .map(|links| links.iter().map(|link| link.link()).collect::<Vec<u32>>())
+        })
+    }
 }
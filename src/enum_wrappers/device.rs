@@ -1,493 +1,1008 @@
-use error::{Result, ErrorKind, Error};
 use ffi::bindings::*;
+use std::os::raw::c_uint;
 
-/// API types that allow changes to default permission restrictions.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlRestrictedAPI_enum")]
-pub enum Api {
+wrap_c_enum! {
+    /// API types that allow changes to default permission restrictions.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Api: nvmlRestrictedAPI_enum {
+        /**
+        APIs that change application clocks.
+        
+        Applicable methods on `Device`: `.set_applications_clocks()`, 
+        `.reset_applications_clocks()`
+        */
+        ApplicationClocks = nvmlRestrictedAPI_enum_NVML_RESTRICTED_API_SET_APPLICATION_CLOCKS,
+        /// APIs that enable/disable auto boosted clocks.
+        ///
+        /// Applicable methods on `Device`: `.set_auto_boosted_clocks()`
+        AutoBoostedClocks = nvmlRestrictedAPI_enum_NVML_RESTRICTED_API_SET_AUTO_BOOSTED_CLOCKS
+    }
+}
+
+display_from_str!(Api {
+    ApplicationClocks => "Application Clocks",
+    AutoBoostedClocks => "Auto Boosted Clocks"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Clock types. All speeds are in MHz.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Clock: nvmlClockType_enum {
+        /// Graphics clock domain.
+        Graphics = nvmlClockType_enum_NVML_CLOCK_GRAPHICS,
+        /// SM (Streaming Multiprocessor) clock domain.
+        ///
+        /// What AMD calls a CU (Compute Unit) can be compared to this.
+        SM = nvmlClockType_enum_NVML_CLOCK_SM,
+        /// Memory clock domain.
+        Memory = nvmlClockType_enum_NVML_CLOCK_MEM,
+        /// Video encoder/decoder clock domain.
+        Video = nvmlClockType_enum_NVML_CLOCK_VIDEO
+    }
+}
+
+display_from_str!(Clock {
+    Graphics => "Graphics",
+    SM => "SM",
+    Memory => "Memory",
+    Video => "Video"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// These are used in combo with `Clock` to specify a single clock value.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum ClockId: nvmlClockId_enum {
+        /// Current actual clock value.
+        Current = nvmlClockId_enum_NVML_CLOCK_ID_CURRENT,
+        /// Target application clock.
+        TargetAppClock = nvmlClockId_enum_NVML_CLOCK_ID_APP_CLOCK_TARGET,
+        /// Default application clock target.
+        DefaultAppClock = nvmlClockId_enum_NVML_CLOCK_ID_APP_CLOCK_DEFAULT,
+        /// OEM-defined maximum clock rate.
+        CustomerMaxBoost = nvmlClockId_enum_NVML_CLOCK_ID_CUSTOMER_BOOST_MAX
+    }
+}
+
+display_from_str!(ClockId {
+    Current => "Current",
+    TargetAppClock => "Applications Clocks",
+    DefaultAppClock => "Default Applications Clocks",
+    CustomerMaxBoost => "Max Customer Boost Clocks"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// GPU brand.
+    ///
+    /// NVML's own "unknown" brand is reported as `Unknown`.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Brand: nvmlBrandType_enum {
+        /// Targeted at workstations.
+        Quadro = nvmlBrandType_enum_NVML_BRAND_QUADRO,
+        /// Targeted at high-end compute.
+        Tesla = nvmlBrandType_enum_NVML_BRAND_TESLA,
+        /// NVIDIA's multi-display cards.
+        NVS = nvmlBrandType_enum_NVML_BRAND_NVS,
+        /// Targeted at virtualization (vGPUs).
+        GRID = nvmlBrandType_enum_NVML_BRAND_GRID,
+        /// Targeted at gaming.
+        GeForce = nvmlBrandType_enum_NVML_BRAND_GEFORCE
+    }
+}
+
+display_from_str!(Brand {
+    Quadro => "Quadro",
+    Tesla => "Tesla",
+    NVS => "NVS",
+    GRID => "GRID",
+    GeForce => "GeForce"
+} unknown Unknown);
+
+wrap_c_enum! {
     /**
-    APIs that change application clocks.
-    
-    Applicable methods on `Device`: `.set_applications_clocks()`, 
-    `.reset_applications_clocks()`
+    Represents type of a bridge chip.
+
+    NVIDIA does not provide docs (in the code, that is) explaining what each chip
+    type is, so you're on your own there.
     */
-    #[wrap(c_variant = "NVML_RESTRICTED_API_SET_APPLICATION_CLOCKS")]
-    ApplicationClocks,
-    /// APIs that enable/disable auto boosted clocks.
-    ///
-    /// Applicable methods on `Device`: `.set_auto_boosted_clocks()`
-    #[wrap(c_variant = "NVML_RESTRICTED_API_SET_AUTO_BOOSTED_CLOCKS")]
-    AutoBoostedClocks
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum BridgeChip: nvmlBridgeChipType_enum {
+        PLX = nvmlBridgeChipType_enum_NVML_BRIDGE_CHIP_PLX,
+        BRO4 = nvmlBridgeChipType_enum_NVML_BRIDGE_CHIP_BRO4
+    }
 }
 
-/// Clock types. All speeds are in MHz.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlClockType_enum")]
-pub enum Clock {
-    /// Graphics clock domain.
-    #[wrap(c_variant = "NVML_CLOCK_GRAPHICS")]
-    Graphics,
-    /// SM (Streaming Multiprocessor) clock domain.
-    ///
-    /// What AMD calls a CU (Compute Unit) can be compared to this.
-    #[wrap(c_variant = "NVML_CLOCK_SM")]
-    SM,
-    /// Memory clock domain.
-    #[wrap(c_variant = "NVML_CLOCK_MEM")]
-    Memory,
-    /// Video encoder/decoder clock domain.
-    #[wrap(c_variant = "NVML_CLOCK_VIDEO")]
-    Video
+display_from_str!(BridgeChip {
+    PLX => "PLX",
+    BRO4 => "BR04"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Memory error types.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum MemoryError: nvmlMemoryErrorType_enum {
+        /**
+        A memory error that was corrected.
+        
+        ECC error: single bit error.
+        Texture memory: error fixed by a resend.
+        */
+        Corrected = nvmlMemoryErrorType_enum_NVML_MEMORY_ERROR_TYPE_CORRECTED,
+        /**
+        A memory error that was not corrected.
+        
+        ECC error: double bit error.
+        Texture memory: error occurred and resend failed.
+        */
+        Uncorrected = nvmlMemoryErrorType_enum_NVML_MEMORY_ERROR_TYPE_UNCORRECTED
+    }
 }
 
-/// These are used in combo with `Clock` to specify a single clock value.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlClockId_enum")]
-pub enum ClockId {
-    /// Current actual clock value.
-    #[wrap(c_variant = "NVML_CLOCK_ID_CURRENT")]
-    Current,
-    /// Target application clock.
-    #[wrap(c_variant = "NVML_CLOCK_ID_APP_CLOCK_TARGET")]
-    TargetAppClock,
-    /// Default application clock target.
-    #[wrap(c_variant = "NVML_CLOCK_ID_APP_CLOCK_DEFAULT")]
-    DefaultAppClock,
-    /// OEM-defined maximum clock rate.
-    #[wrap(c_variant = "NVML_CLOCK_ID_CUSTOMER_BOOST_MAX")]
-    CustomerMaxBoost
-}
-
-/// GPU brand.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlBrandType_enum")]
-pub enum Brand {
-    #[wrap(c_variant = "NVML_BRAND_UNKNOWN")]
-    Unknown,
-    /// Targeted at workstations.
-    #[wrap(c_variant = "NVML_BRAND_QUADRO")]
-    Quadro,
-    /// Targeted at high-end compute.
-    #[wrap(c_variant = "NVML_BRAND_TESLA")]
-    Tesla,
-    /// NVIDIA's multi-display cards.
-    #[wrap(c_variant = "NVML_BRAND_NVS")]
-    NVS,
-    /// Targeted at virtualization (vGPUs).
-    #[wrap(c_variant = "NVML_BRAND_GRID")]
-    GRID,
-    /// Targeted at gaming.
-    #[wrap(c_variant = "NVML_BRAND_GEFORCE")]
-    GeForce
+display_from_str!(MemoryError {
+    Corrected => "Corrected",
+    Uncorrected => "Uncorrected"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /**
+    ECC counter types.
+
+    Note: Volatile counts are reset each time the driver loads. On Windows this is
+    once per boot. On Linux this can be more frequent; the driver unloads when no
+    active clients exist. If persistence mode is enabled or there is always a
+    driver client active (such as X11), then Linux also sees per-boot behavior.
+    If not, volatile counts are reset each time a compute app is run.
+    */
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum EccCounter: nvmlEccCounterType_enum {
+        /// Volatile counts are reset each time the driver loads.
+        Volatile = nvmlEccCounterType_enum_NVML_VOLATILE_ECC,
+        /// Aggregate counts persist across reboots (i.e. for the lifetime of the
+        /// device).
+        Aggregate = nvmlEccCounterType_enum_NVML_AGGREGATE_ECC
+    }
 }
 
-/**
-Represents type of a bridge chip.
+display_from_str!(EccCounter {
+    Volatile => "Volatile",
+    Aggregate => "Aggregate"
+} unknown Unknown);
 
-NVIDIA does not provide docs (in the code, that is) explaining what each chip
-type is, so you're on your own there.
-*/
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlBridgeChipType_enum")]
-pub enum BridgeChip {
-    #[wrap(c_variant = "NVML_BRIDGE_CHIP_PLX")]
-    PLX,
-    #[wrap(c_variant = "NVML_BRIDGE_CHIP_BRO4")]
-    BRO4
+wrap_c_enum! {
+    /// Memory locations. See `Device.memory_error_counter()`.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum MemoryLocation: nvmlMemoryLocation_enum {
+        /// GPU L1 cache.
+        L1Cache = nvmlMemoryLocation_enum_NVML_MEMORY_LOCATION_L1_CACHE,
+        /// GPU L2 cache.
+        L2Cache = nvmlMemoryLocation_enum_NVML_MEMORY_LOCATION_L2_CACHE,
+        /// GPU device memory.
+        Device = nvmlMemoryLocation_enum_NVML_MEMORY_LOCATION_DEVICE_MEMORY,
+        /// GPU register file.
+        RegisterFile = nvmlMemoryLocation_enum_NVML_MEMORY_LOCATION_REGISTER_FILE,
+        /// GPU texture memory.
+        Texture = nvmlMemoryLocation_enum_NVML_MEMORY_LOCATION_TEXTURE_MEMORY,
+        /// Shared memory.
+        Shared = nvmlMemoryLocation_enum_NVML_MEMORY_LOCATION_TEXTURE_SHM
+    }
 }
 
-/// Memory error types.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlMemoryErrorType_enum")]
-pub enum MemoryError {
+display_from_str!(MemoryLocation {
+    L1Cache => "L1 Cache",
+    L2Cache => "L2 Cache",
+    Device => "Device Memory",
+    RegisterFile => "Register File",
+    Texture => "Texture Memory",
+    Shared => "Texture Shared"
+} unknown Unknown);
+
+#[cfg(target_os = "windows")]
+wrap_c_enum! {
+    /// Driver models, Windows only.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum DriverModel: nvmlDriverModel_enum {
+        /// GPU treated as a display device.
+        WDDM = nvmlDriverModel_enum_NVML_DRIVER_WDDM,
+        /// (TCC model) GPU treated as a generic device (recommended).
+        WDM = nvmlDriverModel_enum_NVML_DRIVER_WDM
+    }
+}
+
+#[cfg(target_os = "windows")]
+display_from_str!(DriverModel {
+    WDDM => "WDDM",
+    WDM => "WDM"
+} unknown Unknown);
+
+wrap_c_enum! {
     /**
-    A memory error that was corrected.
-    
-    ECC error: single bit error.
-    Texture memory: error fixed by a resend.
+    GPU operation mode.
+
+    Allows for the reduction of power usage and optimization of GPU throughput
+    by disabling GPU features. Each mode is designed to meet specific needs.
     */
-    #[wrap(c_variant = "NVML_MEMORY_ERROR_TYPE_CORRECTED")]
-    Corrected,
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum OperationMode: nvmlGom_enum {
+        /// Everything is enabled and running at full speed.
+        AllOn = nvmlGom_enum_NVML_GOM_ALL_ON,
+        /// Designed for running only compute tasks; disables graphics operations.
+        Compute = nvmlGom_enum_NVML_GOM_COMPUTE,
+        /// Designed for running graphics applications that don't require high
+        /// bandwidth double precision.
+        LowDP = nvmlGom_enum_NVML_GOM_LOW_DP
+    }
+}
+
+display_from_str!(OperationMode {
+    AllOn => "All On",
+    Compute => "Compute",
+    LowDP => "Low Double Precision"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Available infoROM objects.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum InfoRom: nvmlInforomObject_enum {
+        /// An object defined by OEM.
+        OEM = nvmlInforomObject_enum_NVML_INFOROM_OEM,
+        /// The ECC object determining the level of ECC support.
+        ECC = nvmlInforomObject_enum_NVML_INFOROM_ECC,
+        /// The power management object.
+        Power = nvmlInforomObject_enum_NVML_INFOROM_POWER
+    }
+}
+
+display_from_str!(InfoRom {
+    OEM => "OEM",
+    ECC => "ECC",
+    Power => "Power"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Represents the queryable PCIe utilization counters (in bytes). 1KB
+    /// granularity.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum PcieUtilCounter: nvmlPcieUtilCounter_enum {
+        Send = nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_TX_BYTES,
+        Receive = nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_RX_BYTES
+    }
+}
+
+display_from_str!(PcieUtilCounter {
+    Send => "TX",
+    Receive => "RX"
+} unknown Unknown);
+
+wrap_c_enum! {
     /**
-    A memory error that was not corrected.
-    
-    ECC error: double bit error.
-    Texture memory: error occurred and resend failed.
+    Allowed performance states.
+
+    ```text
+    Value    Performance
+      0       (highest)
+     ...
+     15       (lowest)
+    ```
+
+    NVML's own "unknown" performance state is reported as `Unknown`.
     */
-    #[wrap(c_variant = "NVML_MEMORY_ERROR_TYPE_UNCORRECTED")]
-    Uncorrected
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum PerformanceState: nvmlPStates_enum {
+        /// Maximum performance.
+        Zero = nvmlPStates_enum_NVML_PSTATE_0,
+        One = nvmlPStates_enum_NVML_PSTATE_1,
+        Two = nvmlPStates_enum_NVML_PSTATE_2,
+        Three = nvmlPStates_enum_NVML_PSTATE_3,
+        Four = nvmlPStates_enum_NVML_PSTATE_4,
+        Five = nvmlPStates_enum_NVML_PSTATE_5,
+        Six = nvmlPStates_enum_NVML_PSTATE_6,
+        Seven = nvmlPStates_enum_NVML_PSTATE_7,
+        Eight = nvmlPStates_enum_NVML_PSTATE_8,
+        Nine = nvmlPStates_enum_NVML_PSTATE_9,
+        Ten = nvmlPStates_enum_NVML_PSTATE_10,
+        Eleven = nvmlPStates_enum_NVML_PSTATE_11,
+        Twelve = nvmlPStates_enum_NVML_PSTATE_12,
+        Thirteen = nvmlPStates_enum_NVML_PSTATE_13,
+        Fourteen = nvmlPStates_enum_NVML_PSTATE_14,
+        /// Minimum peformance.
+        Fifteen = nvmlPStates_enum_NVML_PSTATE_15
+    }
 }
 
-/**
-ECC counter types.
+display_from_str!(PerformanceState {
+    Zero => "P0",
+    One => "P1",
+    Two => "P2",
+    Three => "P3",
+    Four => "P4",
+    Five => "P5",
+    Six => "P6",
+    Seven => "P7",
+    Eight => "P8",
+    Nine => "P9",
+    Ten => "P10",
+    Eleven => "P11",
+    Twelve => "P12",
+    Thirteen => "P13",
+    Fourteen => "P14",
+    Fifteen => "P15"
+} unknown Unknown);
 
-Note: Volatile counts are reset each time the driver loads. On Windows this is
-once per boot. On Linux this can be more frequent; the driver unloads when no
-active clients exist. If persistence mode is enabled or there is always a
-driver client active (such as X11), then Linux also sees per-boot behavior.
-If not, volatile counts are reset each time a compute app is run.
-*/
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlEccCounterType_enum")]
-pub enum EccCounter {
-    /// Volatile counts are reset each time the driver loads.
-    #[wrap(c_variant = "NVML_VOLATILE_ECC")]
-    Volatile,
-    /// Aggregate counts persist across reboots (i.e. for the lifetime of the
-    /// device).
-    #[wrap(c_variant = "NVML_AGGREGATE_ECC")]
-    Aggregate
-}
-
-/// Memory locations. See `Device.memory_error_counter()`.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlMemoryLocation_enum")]
-pub enum MemoryLocation {
-    /// GPU L1 cache.
-    #[wrap(c_variant = "NVML_MEMORY_LOCATION_L1_CACHE")]
-    L1Cache,
-    /// GPU L2 cache.
-    #[wrap(c_variant = "NVML_MEMORY_LOCATION_L2_CACHE")]
-    L2Cache,
-    /// GPU device memory.
-    #[wrap(c_variant = "NVML_MEMORY_LOCATION_DEVICE_MEMORY")]
-    Device,
-    /// GPU register file.
-    #[wrap(c_variant = "NVML_MEMORY_LOCATION_REGISTER_FILE")]
-    RegisterFile,
-    /// GPU texture memory.
-    #[wrap(c_variant = "NVML_MEMORY_LOCATION_TEXTURE_MEMORY")]
-    Texture,
-    /// Shared memory.
-    #[wrap(c_variant = "NVML_MEMORY_LOCATION_TEXTURE_SHM")]
-    Shared
-}
-
-/// Driver models, Windows only.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlDriverModel_enum")]
-#[cfg(target_os = "windows")]
-pub enum DriverModel {
-    /// GPU treated as a display device.
-    #[wrap(c_variant = "NVML_DRIVER_WDDM")]
-    WDDM,
-    /// (TCC model) GPU treated as a generic device (recommended).
-    #[wrap(c_variant = "NVML_DRIVER_WDM")]
-    WDM
+wrap_c_enum! {
+    /// Causes for page retirement.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum RetirementCause: nvmlPageRetirementCause_enum {
+        /// Page was retired due to multiple single bit ECC errors.
+        MultipleSingleBitEccErrors = nvmlPageRetirementCause_enum_NVML_PAGE_RETIREMENT_CAUSE_MULTIPLE_SINGLE_BIT_ECC_ERRORS,
+        /// Page was retired due to a single double bit ECC error.
+        DoubleBitEccError = nvmlPageRetirementCause_enum_NVML_PAGE_RETIREMENT_CAUSE_DOUBLE_BIT_ECC_ERROR
+    }
+}
+
+display_from_str!(RetirementCause {
+    MultipleSingleBitEccErrors => "Multiple Single Bit ECC Errors",
+    DoubleBitEccError => "Double Bit ECC Error"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Possible types of sampling events.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Sampling: nvmlSamplingType_enum {
+        /// Total power drawn by GPU.
+        Power = nvmlSamplingType_enum_NVML_TOTAL_POWER_SAMPLES,
+        /// Percent of time during which one or more kernels was executing on the
+        /// GPU.
+        GpuUtilization = nvmlSamplingType_enum_NVML_GPU_UTILIZATION_SAMPLES,
+        /// Percent of time during which global (device) memory was being read or
+        /// written.
+        MemoryUtilization = nvmlSamplingType_enum_NVML_MEMORY_UTILIZATION_SAMPLES,
+        /// Percent of time during which NVENC remains busy.
+        EncoderUtilization = nvmlSamplingType_enum_NVML_ENC_UTILIZATION_SAMPLES,
+        /// Percent of time during which NVDEC remains busy.
+        DecoderUtilization = nvmlSamplingType_enum_NVML_DEC_UTILIZATION_SAMPLES,
+        /// Processor clock samples.
+        ProcessorClock = nvmlSamplingType_enum_NVML_PROCESSOR_CLK_SAMPLES,
+        /// Memory clock samples.
+        MemoryClock = nvmlSamplingType_enum_NVML_MEMORY_CLK_SAMPLES
+    }
 }
 
+display_from_str!(Sampling {
+    Power => "Power",
+    GpuUtilization => "GPU Utilization",
+    MemoryUtilization => "Memory Utilization",
+    EncoderUtilization => "Encoder Utilization",
+    DecoderUtilization => "Decoder Utilization",
+    ProcessorClock => "Processor Clock",
+    MemoryClock => "Memory Clock"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// P2P capability indices, passed to `Device.p2p_status()`.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum TemperatureSensor: nvmlTemperatureSensors_enum {
+        /// Sensor for the GPU die.
+        Gpu = nvmlTemperatureSensors_enum_NVML_TEMPERATURE_GPU
+    }
+}
+
+display_from_str!(TemperatureSensor {
+    Gpu => "GPU"
+} unknown Unknown);
+
+wrap_c_enum! {
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum TemperatureThreshold: nvmlTemperatureThresholds_enum {
+        /// Temperature at which the GPU will shut down for hardware protection.
+        Shutdown = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_SHUTDOWN,
+        /// Temperature at which the GPU will begin to throttle.
+        Slowdown = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_SLOWDOWN,
+        /// Memory temperature at which the GPU will begin to throttle.
+        MemoryMax = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_MEM_MAX,
+        /// GPU temperature at which the GPU can be throttled below the base clock.
+        GpuMax = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_GPU_MAX,
+        /// Minimum GPU temperature that can be set as the acoustic threshold.
+        AcousticMin = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MIN,
+        /// Current acoustic threshold. The GPU will throttle to keep its
+        /// temperature (and fan noise) below this.
+        ///
+        /// This is the only threshold that can be set, via
+        /// `Device.set_temperature_threshold()`.
+        AcousticCurrent = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_CURR,
+        /// Maximum GPU temperature that can be set as the acoustic threshold.
+        AcousticMax = nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MAX
+    }
+}
+
+display_from_str!(TemperatureThreshold {
+    Shutdown => "Shutdown",
+    Slowdown => "Slowdown",
+    MemoryMax => "Memory Max",
+    GpuMax => "GPU Max",
+    AcousticMin => "Acoustic Min",
+    AcousticCurrent => "Acoustic Current",
+    AcousticMax => "Acoustic Max"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Level relationships within a system between two GPUs.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum TopologyLevel: nvmlGpuLevel_enum {
+        /// e.g. Tesla K80.
+        Internal = nvmlGpuLevel_enum_NVML_TOPOLOGY_INTERNAL,
+        /// All devices that only need traverse a single PCIe switch.
+        Single = nvmlGpuLevel_enum_NVML_TOPOLOGY_SINGLE,
+        /// All devices that need not traverse a host bridge.
+        Multiple = nvmlGpuLevel_enum_NVML_TOPOLOGY_MULTIPLE,
+        /// All devices that are connected to the same host bridge.
+        HostBridge = nvmlGpuLevel_enum_NVML_TOPOLOGY_HOSTBRIDGE,
+        /// All devices that are connected to the same CPU but possibly multiple
+        /// host bridges.
+        Cpu = nvmlGpuLevel_enum_NVML_TOPOLOGY_CPU,
+        /// All devices in the system
+        System = nvmlGpuLevel_enum_NVML_TOPOLOGY_SYSTEM
+    }
+}
+
+display_from_str!(TopologyLevel {
+    Internal => "Internal",
+    Single => "PIX",
+    Multiple => "PXB",
+    HostBridge => "PHB",
+    Cpu => "NODE",
+    System => "SYS"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Types of performance policy for which violation times can be queried.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum PerformancePolicy: nvmlPerfPolicyType_enum {
+        Power = nvmlPerfPolicyType_enum_NVML_PERF_POLICY_POWER,
+        Thermal = nvmlPerfPolicyType_enum_NVML_PERF_POLICY_THERMAL,
+        SyncBoost = nvmlPerfPolicyType_enum_NVML_PERF_POLICY_SYNC_BOOST
+    }
+}
+
+display_from_str!(PerformancePolicy {
+    Power => "Power",
+    Thermal => "Thermal",
+    SyncBoost => "Sync Boost"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// `ExclusiveProcess` was added in CUDA 4.0. Earlier CUDA versions supported a
+    /// single exclusive mode, which is equivalent to `ExclusiveThread` in CUDA 4.0
+    /// and beyond.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum ComputeMode: nvmlComputeMode_enum {
+        /// Multiple contexts per device.
+        Default = nvmlComputeMode_enum_NVML_COMPUTEMODE_DEFAULT,
+        /// *SUPPORT REMOVED*
+        ///
+        /// Only one context per device, usable from one thread at a time. *NOT
+        /// SUPPORTED*
+        ExclusiveThread = nvmlComputeMode_enum_NVML_COMPUTEMODE_EXCLUSIVE_THREAD,
+        /// No contexts per device.
+        Prohibited = nvmlComputeMode_enum_NVML_COMPUTEMODE_PROHIBITED,
+        /// Only one context per device, usable from multiple threads at a time.
+        ExclusiveProcess = nvmlComputeMode_enum_NVML_COMPUTEMODE_EXCLUSIVE_PROCESS
+    }
+}
+
+display_from_str!(ComputeMode {
+    Default => "Default",
+    ExclusiveThread => "Exclusive_Thread",
+    Prohibited => "Prohibited",
+    ExclusiveProcess => "Exclusive_Process"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// P2P capability index status.
+    ///
+    /// NVML's own "unknown" status is reported as `Unknown`.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum P2pStatus: nvmlGpuP2PStatus_enum {
+        Ok = nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_OK,
+        ChipsetNotSupported = nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_CHIPSET_NOT_SUPPORED,
+        GpuNotSupported = nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_GPU_NOT_SUPPORTED,
+        IohTopologyNotSupported = nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_IOH_TOPOLOGY_NOT_SUPPORTED,
+        DisabledByRegkey = nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_DISABLED_BY_REGKEY,
+        NotSupported = nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_NOT_SUPPORTED
+    }
+}
+
+display_from_str!(P2pStatus {
+    Ok => "OK",
+    ChipsetNotSupported => "Chipset Not Supported",
+    GpuNotSupported => "GPU Not Supported",
+    IohTopologyNotSupported => "Topology Not Supported",
+    DisabledByRegkey => "Disabled By Regkey",
+    NotSupported => "Not Supported"
+} unknown Unknown);
+
+wrap_c_enum! {
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum P2pCapabilitiesIndex: nvmlGpuP2PCapsIndex_enum {
+        Read = nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_READ,
+        Write = nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_WRITE,
+        NvLink = nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_NVLINK,
+        Atomics = nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_ATOMICS,
+        Prop = nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_PROP
+    }
+}
+
+display_from_str!(P2pCapabilitiesIndex {
+    Read => "Read",
+    Write => "Write",
+    NvLink => "NvLink",
+    Atomics => "Atomics",
+    Prop => "Prop"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Represents types for returned sample values.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum SampleValueType: nvmlValueType_enum {
+        Double = nvmlValueType_enum_NVML_VALUE_TYPE_DOUBLE,
+        UnsignedInt = nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_INT,
+        UnsignedLong = nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_LONG,
+        UnsignedLongLong = nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_LONG_LONG,
+        SignedLongLong = nvmlValueType_enum_NVML_VALUE_TYPE_SIGNED_LONG_LONG,
+        SignedInt = nvmlValueType_enum_NVML_VALUE_TYPE_SIGNED_INT
+    }
+}
+
+display_from_str!(SampleValueType {
+    Double => "Double",
+    UnsignedInt => "Unsigned Int",
+    UnsignedLong => "Unsigned Long",
+    UnsignedLongLong => "Unsigned Long Long",
+    SignedLongLong => "Signed Long Long",
+    SignedInt => "Signed Int"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Fan control policies.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum FanControlPolicy: nvmlFanControlPolicy_t {
+        /// Temperature-controlled fan policy (the default).
+        TemperatureContinuousSw = NVML_FAN_POLICY_TEMPERATURE_CONTINOUS_SW,
+        /// Manual fan control policy, set by `Device.set_fan_speed()`.
+        Manual = NVML_FAN_POLICY_MANUAL
+    }
+}
+
+display_from_str!(FanControlPolicy {
+    TemperatureContinuousSw => "Temperature Continuous SW",
+    Manual => "Manual"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /**
+    GPU architectures.
+
+    NVML's own "unknown" value is reported as `Unknown`, along with
+    architectures this wrapper does not know about yet.
+    */
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum DeviceArchitecture: nvmlDeviceArchitecture_t {
+        Kepler = NVML_DEVICE_ARCH_KEPLER,
+        Maxwell = NVML_DEVICE_ARCH_MAXWELL,
+        Pascal = NVML_DEVICE_ARCH_PASCAL,
+        Volta = NVML_DEVICE_ARCH_VOLTA,
+        Turing = NVML_DEVICE_ARCH_TURING,
+        Ampere = NVML_DEVICE_ARCH_AMPERE,
+        Ada = NVML_DEVICE_ARCH_ADA,
+        Hopper = NVML_DEVICE_ARCH_HOPPER,
+        Blackwell = NVML_DEVICE_ARCH_BLACKWELL
+    }
+}
+
+display_from_str!(DeviceArchitecture {
+    Kepler => "Kepler",
+    Maxwell => "Maxwell",
+    Pascal => "Pascal",
+    Volta => "Volta",
+    Turing => "Turing",
+    Ampere => "Ampere",
+    Ada => "Ada Lovelace",
+    Hopper => "Hopper",
+    Blackwell => "Blackwell"
+} unknown Unknown);
+
 /**
-GPU operation mode.
+Thermal sensor controllers.
 
-Allows for the reduction of power usage and optimization of GPU throughput
-by disabling GPU features. Each mode is designed to meet specific needs.
+NVML defines these as an anonymous C enum with a signed representation, so
+the conversions are written out by hand. Values that this wrapper does not
+recognize (as well as NVML's own "unknown" value) are reported as `Unknown`,
+carrying the raw value.
 */
 // Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlGom_enum")]
-pub enum OperationMode {
-    /// Everything is enabled and running at full speed.
-    #[wrap(c_variant = "NVML_GOM_ALL_ON")]
-    AllOn,
-    /// Designed for running only compute tasks; disables graphics operations.
-    #[wrap(c_variant = "NVML_GOM_COMPUTE")]
-    Compute,
-    /// Designed for running graphics applications that don't require high
-    /// bandwidth double precision.
-    #[wrap(c_variant = "NVML_GOM_LOW_DP")]
-    LowDP
-}
-
-/// Available infoROM objects.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlInforomObject_enum")]
-pub enum InfoRom {
-    /// An object defined by OEM.
-    #[wrap(c_variant = "NVML_INFOROM_OEM")]
-    OEM,
-    /// The ECC object determining the level of ECC support.
-    #[wrap(c_variant = "NVML_INFOROM_ECC")]
-    ECC,
-    /// The power management object.
-    #[wrap(c_variant = "NVML_INFOROM_POWER")]
-    Power
-}
-
-/// Represents the queryable PCIe utilization counters (in bytes). 1KB
-/// granularity.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlPcieUtilCounter_enum")]
-pub enum PcieUtilCounter {
-    #[wrap(c_variant = "NVML_PCIE_UTIL_TX_BYTES")]
-    Send,
-    #[wrap(c_variant = "NVML_PCIE_UTIL_RX_BYTES")]
-    Receive
+pub enum ThermalController {
+    None,
+    GpuInternal,
+    Adm1032,
+    Adt7461,
+    Max6649,
+    Max1617,
+    Lm99,
+    Lm89,
+    Lm64,
+    G781,
+    Adt7473,
+    SbMax6649,
+    VbiosEvt,
+    Os,
+    NvSysConCanoas,
+    NvSysConE551,
+    Max6649R,
+    Adt7473S,
+    Unknown(i32)
+}
+
+display_from_str!(ThermalController {
+    None => "None",
+    GpuInternal => "GPU Internal",
+    Adm1032 => "ADM1032",
+    Adt7461 => "ADT7461",
+    Max6649 => "MAX6649",
+    Max1617 => "MAX1617",
+    Lm99 => "LM99",
+    Lm89 => "LM89",
+    Lm64 => "LM64",
+    G781 => "G781",
+    Adt7473 => "ADT7473",
+    SbMax6649 => "SBMAX6649",
+    VbiosEvt => "VBIOSEVT",
+    Os => "OS",
+    NvSysConCanoas => "NVSYSCON_CANOAS",
+    NvSysConE551 => "NVSYSCON_E551",
+    Max6649R => "MAX6649R",
+    Adt7473S => "ADT7473S"
+} unknown Unknown);
+
+impl ThermalController {
+    /// Returns the C enum variant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlThermalController_t {
+        use self::ThermalController::*;
+
+        match *self {
+            None => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE,
+            GpuInternal => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL,
+            Adm1032 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032,
+            Adt7461 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461,
+            Max6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649,
+            Max1617 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617,
+            Lm99 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99,
+            Lm89 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89,
+            Lm64 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64,
+            G781 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781,
+            Adt7473 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473,
+            SbMax6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649,
+            VbiosEvt => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT,
+            Os => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS,
+            NvSysConCanoas => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS,
+            NvSysConE551 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551,
+            Max6649R => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R,
+            Adt7473S => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S,
+            Unknown(value) => value,
+        }
+    }
+}
+
+impl From<nvmlThermalController_t> for ThermalController {
+    fn from(enum_: nvmlThermalController_t) -> Self {
+        use self::ThermalController::*;
+
+        match enum_ {
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE => None,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL => GpuInternal,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032 => Adm1032,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461 => Adt7461,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649 => Max6649,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617 => Max1617,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99 => Lm99,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89 => Lm89,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64 => Lm64,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781 => G781,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473 => Adt7473,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649 => SbMax6649,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT => VbiosEvt,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS => Os,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS => NvSysConCanoas,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551 => NvSysConE551,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R => Max6649R,
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S => Adt7473S,
+            value => Unknown(value),
+        }
+    }
 }
 
 /**
-Allowed performance states.
-
-```text
-Value    Performance
-  0       (highest)
- ...
- 15       (lowest)
-```
+The part of the board that a thermal sensor measures.
+
+NVML defines these as an anonymous C enum with a signed representation, so
+the conversions are written out by hand. Values that this wrapper does not
+recognize (as well as NVML's own "unknown" value) are reported as `Unknown`,
+carrying the raw value.
 */
 // Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlPStates_enum")]
-pub enum PerformanceState {
-    /// Maximum performance.
-    #[wrap(c_variant = "NVML_PSTATE_0")]
-    Zero,
-    #[wrap(c_variant = "NVML_PSTATE_1")]
-    One,
-    #[wrap(c_variant = "NVML_PSTATE_2")]
-    Two,
-    #[wrap(c_variant = "NVML_PSTATE_3")]
-    Three,
-    #[wrap(c_variant = "NVML_PSTATE_4")]
-    Four,
-    #[wrap(c_variant = "NVML_PSTATE_5")]
-    Five,
-    #[wrap(c_variant = "NVML_PSTATE_6")]
-    Six,
-    #[wrap(c_variant = "NVML_PSTATE_7")]
-    Seven,
-    #[wrap(c_variant = "NVML_PSTATE_8")]
-    Eight,
-    #[wrap(c_variant = "NVML_PSTATE_9")]
-    Nine,
-    #[wrap(c_variant = "NVML_PSTATE_10")]
-    Ten,
-    #[wrap(c_variant = "NVML_PSTATE_11")]
-    Eleven,
-    #[wrap(c_variant = "NVML_PSTATE_12")]
-    Twelve,
-    #[wrap(c_variant = "NVML_PSTATE_13")]
-    Thirteen,
-    #[wrap(c_variant = "NVML_PSTATE_14")]
-    Fourteen,
-    /// Minimum peformance.
-    #[wrap(c_variant = "NVML_PSTATE_15")]
-    Fifteen,
-    /// Unknown performance state.
-    #[wrap(c_variant = "NVML_PSTATE_UNKNOWN")]
-    Unknown
-}
-
-/// Causes for page retirement.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlPageRetirementCause_enum")]
-pub enum RetirementCause {
-    /// Page was retired due to multiple single bit ECC errors.
-    #[wrap(c_variant = "NVML_PAGE_RETIREMENT_CAUSE_MULTIPLE_SINGLE_BIT_ECC_ERRORS")]
-    MultipleSingleBitEccErrors,
-    /// Page was retired due to a single double bit ECC error.
-    #[wrap(c_variant = "NVML_PAGE_RETIREMENT_CAUSE_DOUBLE_BIT_ECC_ERROR")]
-    DoubleBitEccError
+pub enum ThermalTarget {
+    None,
+    /// GPU core temperature.
+    Gpu,
+    /// Memory temperature.
+    Memory,
+    /// Power supply temperature.
+    PowerSupply,
+    /// Board temperature.
+    Board,
+    /// Visual Computing Device board temperature.
+    VcdBoard,
+    /// Visual Computing Device inlet temperature.
+    VcdInlet,
+    /// Visual Computing Device outlet temperature.
+    VcdOutlet,
+    All,
+    Unknown(i32)
 }
 
-/// Possible types of sampling events.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlSamplingType_enum")]
-pub enum Sampling {
-    /// Total power drawn by GPU.
-    #[wrap(c_variant = "NVML_TOTAL_POWER_SAMPLES")]
-    Power,
-    /// Percent of time during which one or more kernels was executing on the
-    /// GPU.
-    #[wrap(c_variant = "NVML_GPU_UTILIZATION_SAMPLES")]
-    GpuUtilization,
-    /// Percent of time during which global (device) memory was being read or
-    /// written.
-    #[wrap(c_variant = "NVML_MEMORY_UTILIZATION_SAMPLES")]
-    MemoryUtilization,
-    /// Percent of time during which NVENC remains busy.
-    #[wrap(c_variant = "NVML_ENC_UTILIZATION_SAMPLES")]
-    EncoderUtilization,
-    /// Percent of time during which NVDEC remains busy.
-    #[wrap(c_variant = "NVML_DEC_UTILIZATION_SAMPLES")]
-    DecoderUtilization,
-    /// Processor clock samples.
-    #[wrap(c_variant = "NVML_PROCESSOR_CLK_SAMPLES")]
-    ProcessorClock,
-    /// Memory clock samples.
-    #[wrap(c_variant = "NVML_MEMORY_CLK_SAMPLES")]
-    MemoryClock
+display_from_str!(ThermalTarget {
+    None => "None",
+    Gpu => "GPU",
+    Memory => "Memory",
+    PowerSupply => "Power Supply",
+    Board => "Board",
+    VcdBoard => "VCD Board",
+    VcdInlet => "VCD Inlet",
+    VcdOutlet => "VCD Outlet",
+    All => "All"
+} unknown Unknown);
+
+impl ThermalTarget {
+    /// Returns the C enum variant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlThermalTarget_t {
+        use self::ThermalTarget::*;
+
+        match *self {
+            None => nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE,
+            Gpu => nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU,
+            Memory => nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY,
+            PowerSupply => nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY,
+            Board => nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD,
+            VcdBoard => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD,
+            VcdInlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET,
+            VcdOutlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET,
+            All => nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL,
+            Unknown(value) => value,
+        }
+    }
 }
 
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlTemperatureSensors_enum")]
-pub enum TemperatureSensor {
-    /// Sensor for the GPU die.
-    #[wrap(c_variant = "NVML_TEMPERATURE_GPU")]
-    Gpu
+impl From<nvmlThermalTarget_t> for ThermalTarget {
+    fn from(enum_: nvmlThermalTarget_t) -> Self {
+        use self::ThermalTarget::*;
+
+        match enum_ {
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE => None,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU => Gpu,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY => Memory,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY => PowerSupply,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD => Board,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD => VcdBoard,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET => VcdInlet,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET => VcdOutlet,
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL => All,
+            value => Unknown(value),
+        }
+    }
 }
 
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlTemperatureThresholds_enum")]
-pub enum TemperatureThreshold {
-    /// Temperature at which the GPU will shut down for hardware protection.
-    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_SHUTDOWN")]
-    Shutdown,
-    /// Temperature at which the GPU will begin to throttle.
-    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_SLOWDOWN")]
-    Slowdown
+wrap_c_enum! {
+    /// The maximum PCIe link speed a device supports.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum PcieLinkMaxSpeed: c_uint {
+        Invalid = NVML_PCIE_LINK_MAX_SPEED_INVALID,
+        /// 2.5 GT/s (gen 1)
+        MegaTransfersPerSecond2500 = NVML_PCIE_LINK_MAX_SPEED_2500MBPS,
+        /// 5 GT/s (gen 2)
+        MegaTransfersPerSecond5000 = NVML_PCIE_LINK_MAX_SPEED_5000MBPS,
+        /// 8 GT/s (gen 3)
+        MegaTransfersPerSecond8000 = NVML_PCIE_LINK_MAX_SPEED_8000MBPS,
+        /// 16 GT/s (gen 4)
+        MegaTransfersPerSecond16000 = NVML_PCIE_LINK_MAX_SPEED_16000MBPS,
+        /// 32 GT/s (gen 5)
+        MegaTransfersPerSecond32000 = NVML_PCIE_LINK_MAX_SPEED_32000MBPS,
+        /// 64 GT/s (gen 6)
+        MegaTransfersPerSecond64000 = NVML_PCIE_LINK_MAX_SPEED_64000MBPS
+    }
 }
 
-/// Level relationships within a system between two GPUs.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlGpuLevel_enum")]
-pub enum TopologyLevel {
-    /// e.g. Tesla K80.
-    #[wrap(c_variant = "NVML_TOPOLOGY_INTERNAL")]
-    Internal,
-    /// All devices that only need traverse a single PCIe switch.
-    #[wrap(c_variant = "NVML_TOPOLOGY_SINGLE")]
-    Single,
-    /// All devices that need not traverse a host bridge.
-    #[wrap(c_variant = "NVML_TOPOLOGY_MULTIPLE")]
-    Multiple,
-    /// All devices that are connected to the same host bridge.
-    #[wrap(c_variant = "NVML_TOPOLOGY_HOSTBRIDGE")]
-    HostBridge,
-    /// All devices that are connected to the same CPU but possibly multiple
-    /// host bridges.
-    #[wrap(c_variant = "NVML_TOPOLOGY_CPU")]
-    Cpu,
-    /// All devices in the system
-    #[wrap(c_variant = "NVML_TOPOLOGY_SYSTEM")]
-    System
-}
-
-/// Types of performance policy for which violation times can be queried.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlPerfPolicyType_enum")]
-pub enum PerformancePolicy {
-    #[wrap(c_variant = "NVML_PERF_POLICY_POWER")]
-    Power,
-    #[wrap(c_variant = "NVML_PERF_POLICY_THERMAL")]
-    Thermal,
-    #[wrap(c_variant = "NVML_PERF_POLICY_SYNC_BOOST")]
-    SyncBoost
-}
-
-/// `ExclusiveProcess` was added in CUDA 4.0. Earlier CUDA versions supported a
-/// single exclusive mode, which is equivalent to `ExclusiveThread` in CUDA 4.0
-/// and beyond.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlComputeMode_enum")]
-pub enum ComputeMode {
-    /// Multiple contexts per device.
-    #[wrap(c_variant = "NVML_COMPUTEMODE_DEFAULT")]
-    Default,
-    /// *SUPPORT REMOVED*
-    ///
-    /// Only one context per device, usable from one thread at a time. *NOT
-    /// SUPPORTED*
-    #[wrap(c_variant = "NVML_COMPUTEMODE_EXCLUSIVE_THREAD")]
-    ExclusiveThread,
-    /// No contexts per device.
-    #[wrap(c_variant = "NVML_COMPUTEMODE_PROHIBITED")]
-    Prohibited,
-    /// Only one context per device, usable from multiple threads at a time.
-    #[wrap(c_variant = "NVML_COMPUTEMODE_EXCLUSIVE_PROCESS")]
-    ExclusiveProcess
-}
-
-/// P2P capability index status.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlGpuP2PStatus_enum")]
-pub enum P2pStatus {
-    #[wrap(c_variant = "NVML_P2P_STATUS_OK")]
-    Ok,
-    #[wrap(c_variant = "NVML_P2P_STATUS_CHIPSET_NOT_SUPPORED")]
-    ChipsetNotSupported,
-    #[wrap(c_variant = "NVML_P2P_STATUS_GPU_NOT_SUPPORTED")]
-    GpuNotSupported,
-    #[wrap(c_variant = "NVML_P2P_STATUS_IOH_TOPOLOGY_NOT_SUPPORTED")]
-    IohTopologyNotSupported,
-    #[wrap(c_variant = "NVML_P2P_STATUS_DISABLED_BY_REGKEY")]
-    DisabledByRegkey,
-    #[wrap(c_variant = "NVML_P2P_STATUS_NOT_SUPPORTED")]
-    NotSupported,
-    #[wrap(c_variant = "NVML_P2P_STATUS_UNKNOWN")]
-    Unknown
+display_from_str!(PcieLinkMaxSpeed {
+    Invalid => "Invalid",
+    MegaTransfersPerSecond2500 => "2.5 GT/s",
+    MegaTransfersPerSecond5000 => "5 GT/s",
+    MegaTransfersPerSecond8000 => "8 GT/s",
+    MegaTransfersPerSecond16000 => "16 GT/s",
+    MegaTransfersPerSecond32000 => "32 GT/s",
+    MegaTransfersPerSecond64000 => "64 GT/s"
+} unknown Unknown);
+
+impl PcieLinkMaxSpeed {
+    /// The speed in MT/s (per lane), if it is known.
+    pub fn as_mega_transfers_per_second(&self) -> Option<u32> {
+        use self::PcieLinkMaxSpeed::*;
+
+        match *self {
+            MegaTransfersPerSecond2500 => Some(2500),
+            MegaTransfersPerSecond5000 => Some(5000),
+            MegaTransfersPerSecond8000 => Some(8000),
+            MegaTransfersPerSecond16000 => Some(16000),
+            MegaTransfersPerSecond32000 => Some(32000),
+            MegaTransfersPerSecond64000 => Some(64000),
+            Invalid | Unknown(_) => None,
+        }
+    }
 }
 
+wrap_c_enum! {
+    /**
+    The type of bus a device is attached to.
+
+    NVML's own "unknown" value is reported as `Unknown`.
+    */
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum BusType: nvmlBusType_t {
+        Pci = NVML_BUS_TYPE_PCI,
+        Pcie = NVML_BUS_TYPE_PCIE,
+        Fpci = NVML_BUS_TYPE_FPCI,
+        Agp = NVML_BUS_TYPE_AGP
+    }
+}
+
+display_from_str!(BusType {
+    Pci => "PCI",
+    Pcie => "PCIe",
+    Fpci => "FPCI",
+    Agp => "AGP"
+} unknown Unknown);
+
+/// The scope to consider when querying affinity.
 // Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlGpuP2PCapsIndex_enum")]
-pub enum P2pCapabilitiesIndex {
-    #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_READ")]
-    Read,
-    #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_WRITE")]
-    Write,
-    #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_NVLINK")]
-    NvLink,
-    #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_ATOMICS")]
-    Atomics,
-    #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_PROP")]
-    Prop,
-    #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_UNKNOWN")]
-    Unknown
-}
-
-/// Represents types for returned sample values.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlValueType_enum")]
-pub enum SampleValueType {
-    #[wrap(c_variant = "NVML_VALUE_TYPE_DOUBLE")]
-    Double,
-    #[wrap(c_variant = "NVML_VALUE_TYPE_UNSIGNED_INT")]
-    UnsignedInt,
-    #[wrap(c_variant = "NVML_VALUE_TYPE_UNSIGNED_LONG")]
-    UnsignedLong,
-    #[wrap(c_variant = "NVML_VALUE_TYPE_UNSIGNED_LONG_LONG")]
-    UnsignedLongLong
+pub enum AffinityScope {
+    /// The NUMA node(s) closest to the device.
+    Node,
+    /// The CPU socket(s) closest to the device.
+    Socket
+}
+
+display_from_str!(AffinityScope {
+    Node => "Node",
+    Socket => "Socket"
+});
+
+impl AffinityScope {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlAffinityScope_t {
+        match *self {
+            AffinityScope::Node => NVML_AFFINITY_SCOPE_NODE,
+            AffinityScope::Socket => NVML_AFFINITY_SCOPE_SOCKET,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use error::{Error, ErrorKind};
+
+    #[test]
+    fn unrecognized_values_are_preserved() {
+        let brand = Brand::from(9001);
+
+        assert_eq!(brand, Brand::Unknown(9001));
+        assert_eq!(brand.as_c(), 9001);
+        assert_eq!(
+            Brand::from(nvmlBrandType_enum_NVML_BRAND_UNKNOWN),
+            Brand::Unknown(nvmlBrandType_enum_NVML_BRAND_UNKNOWN)
+        );
+        assert_eq!(Brand::from(Brand::Tesla.as_c()), Brand::Tesla);
+        assert_eq!(FanControlPolicy::from(2), FanControlPolicy::Unknown(2));
+    }
+
+    #[test]
+    fn display_from_str_round_trip() {
+        for state in &[PerformanceState::Zero, PerformanceState::Fifteen, PerformanceState::Unknown(32)] {
+            assert_eq!(&state.to_string().parse::<PerformanceState>().unwrap(), state);
+        }
+
+        assert_eq!(ComputeMode::ExclusiveProcess.to_string(), "Exclusive_Process");
+        assert_eq!(
+            "exclusive_process".parse::<ComputeMode>().unwrap(),
+            ComputeMode::ExclusiveProcess
+        );
+        assert_eq!(" P2 ".parse::<PerformanceState>().unwrap(), PerformanceState::Two);
+    }
+
+    #[test]
+    fn unknown_variant_round_trip() {
+        let arch = DeviceArchitecture::Unknown(42);
+
+        assert_eq!(arch.to_string(), "Unknown (42)");
+        assert_eq!("Unknown (42)".parse::<DeviceArchitecture>().unwrap(), arch);
+        assert_eq!("unknown (42)".parse::<DeviceArchitecture>().unwrap(), arch);
+    }
+
+    #[test]
+    fn unexpected_string() {
+        match "P16".parse::<PerformanceState>() {
+            Err(Error(ErrorKind::UnexpectedString(name, ref value), _)) => {
+                assert_eq!(name, "PerformanceState");
+                assert_eq!(value, "P16");
+            },
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert!("Unknown (x)".parse::<DeviceArchitecture>().is_err());
+    }
 }
@@ -1,62 +1,97 @@
-use error::{Result, ErrorKind, Error};
 use ffi::bindings::*;
 
-/// Represents the NvLink utilization counter packet units.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlNvLinkUtilizationCountUnits_enum")]
-pub enum UtilizationCountUnit {
-    #[wrap(c_variant = "NVML_NVLINK_COUNTER_UNIT_CYCLES")]
-    Cycles,
-    #[wrap(c_variant = "NVML_NVLINK_COUNTER_UNIT_PACKETS")]
-    Packets,
-    #[wrap(c_variant = "NVML_NVLINK_COUNTER_UNIT_BYTES")]
-    Bytes
+wrap_c_enum! {
+    /// Represents the NvLink utilization counter packet units.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum UtilizationCountUnit: nvmlNvLinkUtilizationCountUnits_enum {
+        Cycles = nvmlNvLinkUtilizationCountUnits_enum_NVML_NVLINK_COUNTER_UNIT_CYCLES,
+        Packets = nvmlNvLinkUtilizationCountUnits_enum_NVML_NVLINK_COUNTER_UNIT_PACKETS,
+        Bytes = nvmlNvLinkUtilizationCountUnits_enum_NVML_NVLINK_COUNTER_UNIT_BYTES
+    }
 }
 
-/// Represents queryable NvLink capabilities.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlNvLinkCapability_enum")]
-pub enum Capability {
-    /// P2P over NVLink is supported.
-    #[wrap(c_variant = "NVML_NVLINK_CAP_P2P_SUPPORTED")]
-    P2p,
-    /// Access to system memory is supported.
-    #[wrap(c_variant = "NVML_NVLINK_CAP_SYSMEM_ACCESS")]
-    SysMemAccess,
-    /// P2P atomics are supported.
-    #[wrap(c_variant = "NVML_NVLINK_CAP_P2P_ATOMICS")]
-    P2pAtomics,
-    /// System memory atomics are supported.
-    #[wrap(c_variant = "NVML_NVLINK_CAP_SYSMEM_ATOMICS")]
-    SysMemAtomics,
-    /// SLI is supported over this link.
-    #[wrap(c_variant = "NVML_NVLINK_CAP_SLI_BRIDGE")]
-    SliBridge,
-    /// Link is supported on this device.
-    #[wrap(c_variant = "NVML_NVLINK_CAP_VALID")]
-    ValidLink
+display_from_str!(UtilizationCountUnit {
+    Cycles => "Cycles",
+    Packets => "Packets",
+    Bytes => "Bytes"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Represents queryable NvLink capabilities.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Capability: nvmlNvLinkCapability_enum {
+        /// P2P over NVLink is supported.
+        P2p = nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_P2P_SUPPORTED,
+        /// Access to system memory is supported.
+        SysMemAccess = nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_SYSMEM_ACCESS,
+        /// P2P atomics are supported.
+        P2pAtomics = nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_P2P_ATOMICS,
+        /// System memory atomics are supported.
+        SysMemAtomics = nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_SYSMEM_ATOMICS,
+        /// SLI is supported over this link.
+        SliBridge = nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_SLI_BRIDGE,
+        /// Link is supported on this device.
+        ValidLink = nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_VALID
+    }
 }
 
-/// Represents queryable NvLink error counters.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlNvLinkErrorCounter_enum")]
-pub enum ErrorCounter {
-    /// Data link transmit replay error counter.
-    #[wrap(c_variant = "NVML_NVLINK_ERROR_DL_REPLAY")]
-    DlReplay,
-    /// Data link transmit recovery error counter.
-    #[wrap(c_variant = "NVML_NVLINK_ERROR_DL_RECOVERY")]
-    DlRecovery,
-    /// Data link receive flow control digit CRC error counter.
-    #[wrap(c_variant = "NVML_NVLINK_ERROR_DL_CRC_FLIT")]
-    DlCrcFlit,
-    /// Data link receive data CRC error counter.
-    #[wrap(c_variant = "NVML_NVLINK_ERROR_DL_CRC_DATA")]
-    DlCrcData
+display_from_str!(Capability {
+    P2p => "P2P",
+    SysMemAccess => "System Memory Access",
+    P2pAtomics => "P2P Atomics",
+    SysMemAtomics => "System Memory Atomics",
+    SliBridge => "SLI Bridge",
+    ValidLink => "Valid Link"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /// Represents queryable NvLink error counters.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum ErrorCounter: nvmlNvLinkErrorCounter_enum {
+        /// Data link transmit replay error counter.
+        DlReplay = nvmlNvLinkErrorCounter_enum_NVML_NVLINK_ERROR_DL_REPLAY,
+        /// Data link transmit recovery error counter.
+        DlRecovery = nvmlNvLinkErrorCounter_enum_NVML_NVLINK_ERROR_DL_RECOVERY,
+        /// Data link receive flow control digit CRC error counter.
+        DlCrcFlit = nvmlNvLinkErrorCounter_enum_NVML_NVLINK_ERROR_DL_CRC_FLIT,
+        /// Data link receive data CRC error counter.
+        DlCrcData = nvmlNvLinkErrorCounter_enum_NVML_NVLINK_ERROR_DL_CRC_DATA
+    }
 }
+
+display_from_str!(ErrorCounter {
+    DlReplay => "Replay",
+    DlRecovery => "Recovery",
+    DlCrcFlit => "CRC FLIT",
+    DlCrcData => "CRC Data"
+} unknown Unknown);
+
+wrap_c_enum! {
+    /**
+    The type of device on the other end of an NvLink.
+
+    NVML's own "unknown" value is reported as `Unknown`.
+    */
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum RemoteDeviceType: nvmlIntNvLinkDeviceType_t {
+        Gpu = NVML_NVLINK_DEVICE_TYPE_GPU,
+        /// An IBM NPU, i.e. the CPU of a POWER system.
+        IbmNpu = NVML_NVLINK_DEVICE_TYPE_IBMNPU,
+        /// An NVSwitch.
+        Switch = NVML_NVLINK_DEVICE_TYPE_SWITCH
+    }
+}
+
+display_from_str!(RemoteDeviceType {
+    Gpu => "GPU",
+    IbmNpu => "IBM NPU",
+    Switch => "Switch"
+} unknown Unknown);
@@ -1,28 +1,35 @@
-use error::{Result, ErrorKind, Error};
 use ffi::bindings::*;
 
-/// Unit fan state.
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlFanState_enum")]
-pub enum FanState {
-    /// Working properly
-    #[wrap(c_variant = "NVML_FAN_NORMAL")]
-    Normal,
-    #[wrap(c_variant = "NVML_FAN_FAILED")]
-    Failed
+wrap_c_enum! {
+    /// Unit fan state.
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum FanState: nvmlFanState_enum {
+        /// Working properly
+        Normal = nvmlFanState_enum_NVML_FAN_NORMAL,
+        Failed = nvmlFanState_enum_NVML_FAN_FAILED
+    }
 }
 
-// Checked against local
-#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[wrap(c_enum = "nvmlLedColor_enum")]
-pub enum LedColor {
-    /// Used to indicate good health.
-    #[wrap(c_variant = "NVML_LED_COLOR_GREEN")]
-    Green,
-    /// Used to indicate a problem.
-    #[wrap(c_variant = "NVML_LED_COLOR_AMBER")]
-    Amber
+display_from_str!(FanState {
+    Normal => "Normal",
+    Failed => "Failed"
+} unknown Unknown);
+
+wrap_c_enum! {
+    // Checked against local
+    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum LedColor: nvmlLedColor_enum {
+        /// Used to indicate good health.
+        Green = nvmlLedColor_enum_NVML_LED_COLOR_GREEN,
+        /// Used to indicate a problem.
+        Amber = nvmlLedColor_enum_NVML_LED_COLOR_AMBER
+    }
 }
+
+display_from_str!(LedColor {
+    Green => "Green",
+    Amber => "Amber"
+} unknown Unknown);
@@ -1,5 +1,6 @@
 use enum_wrappers::device::SampleValueType;
 use ffi::bindings::*;
+use std::{fmt, mem};
 
 /// Respresents possible variants for a firmware version.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -19,6 +20,15 @@ impl From<u32> for FirmwareVersion {
     }
 }
 
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FirmwareVersion::Unavailable => f.write_str("N/A"),
+            FirmwareVersion::Version(version) => write!(f, "{}", version),
+        }
+    }
+}
+
 /// Represents possible variants for used GPU memory.
 // Checked
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -42,6 +52,16 @@ impl From<u64> for UsedGpuMemory {
     }
 }
 
+impl fmt::Display for UsedGpuMemory {
+    /// Displays the memory in MiB, as nvidia-smi does.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UsedGpuMemory::Unavailable => f.write_str("N/A"),
+            UsedGpuMemory::Used(bytes) => write!(f, "{} MiB", bytes / (1024 * 1024)),
+        }
+    }
+}
+
 /// Represents different types of sample values.
 // Checked against local
 #[derive(Debug, Clone, PartialEq)]
@@ -49,7 +69,12 @@ impl From<u64> for UsedGpuMemory {
 pub enum SampleValue {
     F64(f64),
     U32(u32),
-    U64(u64)
+    U64(u64),
+    I32(i32),
+    I64(i64),
+    /// A value of a type this version of the wrapper does not recognize: the
+    /// type NVML reported and the raw bytes of the value.
+    Unknown(u32, [u8; 8])
 }
 
 impl SampleValue {
@@ -64,7 +89,56 @@ impl SampleValue {
                 // NVML wouldn't return anything larger
                 UnsignedLong => SampleValue::U32(union.ulVal as u32),
                 UnsignedLongLong => SampleValue::U64(union.ullVal),
+                SignedInt => SampleValue::I32(union.siVal),
+                SignedLongLong => SampleValue::I64(union.sllVal),
+                Unknown(value_type) => {
+                    SampleValue::Unknown(value_type, mem::transmute::<nvmlValue_t, [u8; 8]>(union))
+                },
             }
         }
     }
+
+    /// The value as an `f64`, or `None` if its type is unknown. 64-bit integers
+    /// with a magnitude above 2^53 lose precision.
+    #[inline]
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            SampleValue::F64(v) => Some(v),
+            SampleValue::U32(v) => Some(f64::from(v)),
+            SampleValue::U64(v) => Some(v as f64),
+            SampleValue::I32(v) => Some(f64::from(v)),
+            SampleValue::I64(v) => Some(v as f64),
+            SampleValue::Unknown(..) => None,
+        }
+    }
+
+    /// The value as a `u32`, or `None` if its type is unknown. Larger values are
+    /// truncated, negative values are read as 0 and fractional values are
+    /// rounded towards zero.
+    #[inline]
+    pub fn as_u32(&self) -> Option<u32> {
+        match *self {
+            SampleValue::F64(v) => Some(v as u32),
+            SampleValue::U32(v) => Some(v),
+            SampleValue::U64(v) => Some(v as u32),
+            SampleValue::I32(v) if v < 0 => Some(0),
+            SampleValue::I32(v) => Some(v as u32),
+            SampleValue::I64(v) if v < 0 => Some(0),
+            SampleValue::I64(v) => Some(v as u32),
+            SampleValue::Unknown(..) => None,
+        }
+    }
+}
+
+impl fmt::Display for SampleValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SampleValue::F64(v) => write!(f, "{}", v),
+            SampleValue::U32(v) => write!(f, "{}", v),
+            SampleValue::U64(v) => write!(f, "{}", v),
+            SampleValue::I32(v) => write!(f, "{}", v),
+            SampleValue::I64(v) => write!(f, "{}", v),
+            SampleValue::Unknown(value_type, _) => write!(f, "Unknown (type {})", value_type),
+        }
+    }
 }
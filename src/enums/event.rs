@@ -1,3 +1,5 @@
+use std::fmt;
+
 /// A simple wrapper used to encode the `Unknown` value into the type system.
 ///
 /// `Unknown` would otherwise be a value of 999 (if it were not an enum
@@ -10,3 +12,12 @@ pub enum XidError {
     /// If the error is unknown.
     Unknown
 }
+
+impl fmt::Display for XidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            XidError::Value(value) => write!(f, "{}", value),
+            XidError::Unknown => f.write_str("Unknown"),
+        }
+    }
+}
@@ -8,3 +8,8 @@ pub enum Counter {
     Zero = 0,
     One = 1
 }
+
+display_from_str!(Counter {
+    Zero => "0",
+    One => "1"
+});
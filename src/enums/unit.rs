@@ -1,6 +1,7 @@
 use error::{Result, ErrorKind, Error};
 use ffi::bindings::*;
 use std::ffi::CStr;
+use std::fmt;
 
 /// LED states for an S-class unit.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -34,6 +35,15 @@ impl LedState {
     }
 }
 
+impl fmt::Display for LedState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LedState::Green => f.write_str("Green"),
+            LedState::Amber(ref cause) => write!(f, "Amber ({})", cause),
+        }
+    }
+}
+
 /// The type of temperature reading to take for a `Unit`.
 ///
 /// Available readings depend on the product.
@@ -45,3 +55,9 @@ pub enum TemperatureReading {
     Exhaust = 1,
     Board = 2
 }
+
+display_from_str!(TemperatureReading {
+    Intake => "Intake",
+    Exhaust => "Exhaust",
+    Board => "Board"
+});
@@ -11,6 +11,7 @@ error_chain! {
     foreign_links {
         Utf8Error(::std::str::Utf8Error);
         NulError(::std::ffi::NulError);
+        Io(::std::io::Error);
     }
 
     errors {
@@ -89,6 +90,60 @@ error_chain! {
                      mapped to a defined enum variant.", value)
         }
 
+        /**
+        A string did not name any variant of an enum.
+
+        This error is specific to this Rust wrapper. It is returned by the
+        `FromStr` implementations of the wrapper's enums.
+
+        The `type_name` field contains the name of the enum and the `value`
+        field contains the string that could not be parsed.
+        */
+        UnexpectedString(type_name: &'static str, value: String) {
+            description("A string did not name any variant of an enum.")
+            display("The string '{}' does not name a variant of `{}`.", value, type_name)
+        }
+
+        /**
+        A fan speed below the configured floor was requested while the GPU
+        temperature was above the configured threshold.
+
+        This error is specific to this Rust wrapper. It is returned by
+        `high_level::FanGuard`.
+        */
+        FanSpeedBelowFloor(requested: u32, floor: u32, temperature: u32) {
+            description("A fan speed below the configured floor was requested while \
+                         the GPU was too hot.")
+            display("A fan speed of '{}%' was requested, but the floor is '{}%' while \
+                     the GPU temperature is '{}' C.", requested, floor, temperature)
+        }
+
+        /**
+        A requested clock range was invalid or fell outside of the range
+        supported by the device.
+
+        Values are in MHz. This error is specific to this Rust wrapper.
+        */
+        ClockRangeOutOfBounds(min: u32, max: u32, supported_min: u32, supported_max: u32) {
+            description("A requested clock range was invalid or fell outside of the range \
+                         supported by the device.")
+            display("The clock range '{}-{}' MHz was requested, but the device supports \
+                     '{}-{}' MHz.", min, max, supported_min, supported_max)
+        }
+
+        /**
+        A requested clock offset fell outside of the range allowed by the
+        device.
+
+        Values are in MHz. This error is specific to this Rust wrapper.
+        */
+        ClockOffsetOutOfRange(offset: i32, min_offset: i32, max_offset: i32) {
+            description("A requested clock offset fell outside of the range allowed by \
+                         the device.")
+            display("The clock offset '{}' MHz was requested, but the device allows \
+                     offsets from '{}' to '{}' MHz.", offset, min_offset, max_offset)
+        }
+
         /// NVML was not first initialized with `NVML::init()`.
         Uninitialized {
             description("NVML was not first initialized with `NVML::init()`.")
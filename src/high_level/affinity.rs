@@ -0,0 +1,342 @@
+/*!
+Pin arbitrary threads and processes to the CPUs closest to a `Device`.
+
+`Device.set_cpu_affinity()` only affects the calling thread. The functions
+here compute the ideal `CpuSet` for a `Device` and apply it with
+`sched_setaffinity` to any thread (or every thread of a process), so that a
+supervisor can pin its workers without each worker linking NVML:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+#
+# #[cfg(target_os = "linux")]
+# fn main() {
+#     example::actual_main().unwrap();
+# }
+#
+# #[cfg(target_os = "windows")]
+# fn main() {}
+#
+# #[cfg(target_os = "linux")]
+# mod example {
+# use nvml::NVML;
+# use nvml::error::Result;
+use nvml::high_level::affinity;
+use std::process::Command;
+#
+# pub fn actual_main() -> Result<()> {
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+
+let worker = Command::new("my-data-loader").spawn()?;
+let previous = affinity::pin_process(&device, worker.id())?;
+
+// ... later, if the worker should be allowed to run anywhere again
+previous.restore()?;
+# Ok(())
+# }
+# }
+```
+
+Threads are identified by their kernel thread ID; a process ID is the thread
+ID of its main thread. `0` refers to the calling thread.
+*/
+
+use Device;
+use error::Result;
+use libc;
+use std::fs;
+use std::io;
+use std::mem;
+use std::os::raw::c_ulong;
+use structs::device::CpuSet;
+
+/**
+The affinity masks threads had before they were pinned.
+
+Returned from `pin_thread()` and `pin_process()`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SavedAffinity {
+    masks: Vec<(u32, CpuSet)>
+}
+
+impl SavedAffinity {
+    /// The thread IDs and the masks they had, in the order they were pinned.
+    #[inline]
+    pub fn masks(&self) -> &[(u32, CpuSet)] {
+        &self.masks
+    }
+
+    /**
+    Restore every thread to the mask it had before it was pinned.
+
+    Threads that have exited in the meantime are skipped.
+
+    # Errors
+
+    * `Io`, if a mask could not be applied (for example due to a lack of
+      permission)
+    */
+    #[inline]
+    pub fn restore(self) -> Result<()> {
+        for (tid, cpus) in self.masks {
+            match set_thread_affinity(tid, &cpus) {
+                Err(ref e) if is_no_such_thread(e) => {},
+                other => other?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Gets the ideal CPU set for the given `Device`.
+
+This is `Device.cpu_affinity_set()`, sized to cover every CPU configured in the
+system.
+
+# Errors
+
+* `Uninitialized`, if the library has not been successfully initialized
+* `InvalidArg`, if the device is invalid
+* `NotSupported`, if the `Device` does not support this feature
+* `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+* `Unknown`, on any unexpected error
+
+# Device Support
+
+Supports Kepler or newer fully supported devices.
+*/
+#[inline]
+pub fn ideal_cpu_set(device: &Device) -> Result<CpuSet> {
+    device.cpu_affinity_set(mask_words())
+}
+
+/**
+Gets the affinity mask of the given thread.
+
+# Errors
+
+* `Io`, if the mask could not be read (for example because the thread does not
+  exist)
+*/
+#[inline]
+pub fn thread_affinity(tid: u32) -> Result<CpuSet> {
+    let mut words = mask_words();
+
+    loop {
+        let mut mask: Vec<c_ulong> = vec![0; words];
+        let size = words * mem::size_of::<c_ulong>();
+
+        let res = unsafe {
+            libc::sched_getaffinity(
+                tid as libc::pid_t,
+                size,
+                mask.as_mut_ptr() as *mut libc::cpu_set_t
+            )
+        };
+
+        if res == 0 {
+            return Ok(CpuSet::from_bitmask(&mask));
+        }
+
+        let error = io::Error::last_os_error();
+        // The kernel's mask is larger than ours; try again with a bigger one
+        if error.raw_os_error() == Some(libc::EINVAL) && words < MAX_MASK_WORDS {
+            words *= 2;
+            continue;
+        }
+
+        return Err(error.into());
+    }
+}
+
+/**
+Sets the affinity mask of the given thread.
+
+# Errors
+
+* `Io`, if the mask could not be applied (for example because the thread does
+  not exist, `cpus` contains no online CPUs, or the caller lacks permission)
+*/
+#[inline]
+pub fn set_thread_affinity(tid: u32, cpus: &CpuSet) -> Result<()> {
+    let mask = cpus.to_bitmask();
+    let size = mask.len() * mem::size_of::<c_ulong>();
+
+    let res = unsafe {
+        libc::sched_setaffinity(
+            tid as libc::pid_t,
+            size,
+            mask.as_ptr() as *const libc::cpu_set_t
+        )
+    };
+
+    if res != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    Ok(())
+}
+
+/**
+Pins the given thread to the ideal CPU set for the given `Device`.
+
+Returns the mask the thread had before, which can be used to restore it.
+
+# Errors
+
+* `Io`, if the thread's mask could not be read or applied
+* `Uninitialized`, if the library has not been successfully initialized
+* `InvalidArg`, if the device is invalid
+* `NotSupported`, if the `Device` does not support this feature
+* `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+* `Unknown`, on any unexpected error
+
+# Device Support
+
+Supports Kepler or newer fully supported devices.
+*/
+#[inline]
+pub fn pin_thread(device: &Device, tid: u32) -> Result<SavedAffinity> {
+    let cpus = ideal_cpu_set(device)?;
+    let previous = thread_affinity(tid)?;
+
+    set_thread_affinity(tid, &cpus)?;
+
+    Ok(SavedAffinity {
+        masks: vec![(tid, previous)]
+    })
+}
+
+/**
+Pins every thread of the given process to the ideal CPU set for the given
+`Device`.
+
+Threads are listed via `/proc/<pid>/task`. Threads that exit while the process
+is being pinned are skipped; threads started afterwards inherit the affinity
+of the thread that created them.
+
+Returns the masks the threads had before, which can be used to restore them.
+If pinning fails partway through, the threads that were already pinned are
+restored before the error is returned.
+
+# Errors
+
+* `Io`, if the process's threads could not be listed or a mask could not be
+  read or applied
+* `Uninitialized`, if the library has not been successfully initialized
+* `InvalidArg`, if the device is invalid
+* `NotSupported`, if the `Device` does not support this feature
+* `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+* `Unknown`, on any unexpected error
+
+# Device Support
+
+Supports Kepler or newer fully supported devices.
+*/
+#[inline]
+pub fn pin_process(device: &Device, pid: u32) -> Result<SavedAffinity> {
+    let cpus = ideal_cpu_set(device)?;
+    let mut saved = SavedAffinity { masks: Vec::new() };
+
+    for tid in process_threads(pid)? {
+        let res = thread_affinity(tid).and_then(|previous| {
+            set_thread_affinity(tid, &cpus)?;
+            Ok(previous)
+        });
+
+        match res {
+            Ok(previous) => saved.masks.push((tid, previous)),
+            Err(ref e) if is_no_such_thread(e) => {},
+            Err(e) => {
+                // Best effort; the original error is the interesting one
+                let _ = saved.restore();
+                return Err(e);
+            },
+        }
+    }
+
+    Ok(saved)
+}
+
+// Upper bound on the size of the mask used when reading affinity, in words.
+// 8192 CPUs is the kernel's current maximum.
+const MAX_MASK_WORDS: usize = 8192 / 32;
+
+// The number of words needed to cover every CPU configured in the system.
+fn mask_words() -> usize {
+    let bits = mem::size_of::<c_ulong>() * 8;
+    let cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    let cpus = if cpus < 1 { 1 } else { cpus as usize };
+
+    (cpus + bits - 1) / bits
+}
+
+fn process_threads(pid: u32) -> Result<Vec<u32>> {
+    let path = if pid == 0 {
+        "/proc/self/task".to_string()
+    } else {
+        format!("/proc/{}/task", pid)
+    };
+
+    let mut tids = Vec::new();
+    for entry in fs::read_dir(path)? {
+        if let Some(tid) = entry?.file_name().to_str().and_then(|s| s.parse().ok()) {
+            tids.push(tid);
+        }
+    }
+
+    Ok(tids)
+}
+
+fn is_no_such_thread(error: &::error::Error) -> bool {
+    match *error.kind() {
+        ::error::ErrorKind::Io(ref e) => e.raw_os_error() == Some(libc::ESRCH),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    #[test]
+    fn thread_affinity_round_trip() {
+        let cpus = thread_affinity(0).expect("read affinity");
+        assert!(!cpus.is_empty());
+
+        set_thread_affinity(0, &cpus).expect("set affinity");
+        assert_eq!(thread_affinity(0).expect("read affinity"), cpus);
+    }
+
+    #[test]
+    fn process_threads_includes_caller() {
+        let tids = process_threads(0).expect("listed threads");
+        let tid = unsafe { libc::syscall(libc::SYS_gettid) } as u32;
+
+        assert!(tids.contains(&tid));
+    }
+
+    #[test]
+    fn ideal_cpu_set() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| super::ideal_cpu_set(device))
+    }
+
+    #[test]
+    fn pin_and_restore_thread() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let before = thread_affinity(0).expect("read affinity");
+
+        let saved = pin_thread(&device, 0).expect("pinned thread");
+        saved.restore().expect("restored thread");
+
+        assert_eq!(thread_affinity(0).expect("read affinity"), before);
+    }
+}
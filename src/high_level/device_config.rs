@@ -0,0 +1,612 @@
+/*!
+Describe the configuration a `Device` should have, and converge on it.
+
+Instead of calling `Device.set_persistent()`, `Device.set_ecc()` and friends
+one by one, a `DesiredConfig` lists the settings a `Device` should end up with.
+`.plan()` compares them against the `Device`'s current state, and `.apply()`
+makes only the changes that are needed:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::enum_wrappers::device::ComputeMode;
+use nvml::high_level::DesiredConfig;
+
+let nvml = NVML::init()?;
+let config = DesiredConfig {
+    persistent: Some(true),
+    compute_mode: Some(ComputeMode::ExclusiveProcess),
+    ecc: Some(true),
+    power_limit: Some(200_000),
+    ..DesiredConfig::default()
+};
+
+for index in 0..nvml.device_count()? {
+    let mut device = nvml.device_by_index(index)?;
+
+    if config.applies_to(&device)? {
+        let applied = config.apply(&mut device)?;
+        // e.g. "ECC: Disabled -> Enabled (requires a reset)"
+        println!("{}", applied);
+
+        if applied.requires_reset() {
+            println!("GPU {} must be reset for its configuration to take effect", index);
+        }
+    }
+}
+# Ok(())
+# }
+```
+
+With the `serde` feature enabled a `DesiredConfig` can be loaded from a config
+file. Fields missing from the config are left alone, and `devices` selects
+which `Device`s the config is meant for.
+*/
+
+use Device;
+use enum_wrappers::device::{Api, Clock, ComputeMode};
+#[cfg(not(target_os = "linux"))]
+use error::ErrorKind;
+use error::Result;
+use std::fmt;
+use structs::device::EccModeState;
+use utils::enabled_disabled;
+
+/// Selects the `Device`s a `DesiredConfig` is meant for.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeviceSelector {
+    /// Every `Device`.
+    All,
+    /// The `Device` with the given index, see `Device.index()`.
+    Index(u32),
+    /// The `Device` with the given UUID, see `Device.uuid()`.
+    Uuid(String),
+    /// The `Device` with the given serial number, see `Device.serial()`.
+    Serial(String),
+    /// The `Device` with the given PCI bus ID, see `PciInfo.bus_id`. Compared
+    /// ignoring case.
+    PciBusId(String),
+    /// Every `Device` with the given product name, see `Device.name()`.
+    Name(String)
+}
+
+impl Default for DeviceSelector {
+    fn default() -> Self {
+        DeviceSelector::All
+    }
+}
+
+impl DeviceSelector {
+    /**
+    Whether the given `Device` is selected.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` does not report the selected property
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    pub fn matches(&self, device: &Device) -> Result<bool> {
+        Ok(match *self {
+            DeviceSelector::All => true,
+            DeviceSelector::Index(index) => device.index()? == index,
+            DeviceSelector::Uuid(ref uuid) => device.uuid()? == *uuid,
+            DeviceSelector::Serial(ref serial) => device.serial()? == *serial,
+            DeviceSelector::PciBusId(ref bus_id) => {
+                device.pci_info()?.bus_id.to_lowercase() == bus_id.to_lowercase()
+            },
+            DeviceSelector::Name(ref name) => device.name()? == *name,
+        })
+    }
+}
+
+/// Applications clocks, in MHz.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApplicationsClocks {
+    pub memory: u32,
+    pub graphics: u32
+}
+
+impl fmt::Display for ApplicationsClocks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Memory: {} MHz, Graphics: {} MHz", self.memory, self.graphics)
+    }
+}
+
+/**
+The settings a `Device` should have.
+
+Settings that are `None` are left as they are. Every setting requires
+root/admin permissions to change.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DesiredConfig {
+    /// The `Device`s this config is meant for. Only consulted by
+    /// `.applies_to()`.
+    pub devices: DeviceSelector,
+    /// See `Device.set_persistent()`. Only supported on Linux.
+    pub persistent: Option<bool>,
+    /// See `Device.set_compute_mode()`.
+    pub compute_mode: Option<ComputeMode>,
+    /// See `Device.set_ecc()`. Takes effect after a reset.
+    pub ecc: Option<bool>,
+    /// See `Device.set_power_management_limit()`, in milliwatts.
+    pub power_limit: Option<u32>,
+    /// See `Device.set_applications_clocks()`.
+    pub applications_clocks: Option<ApplicationsClocks>,
+    /// See `Device.set_accounting()`.
+    pub accounting: Option<bool>,
+    /// Whether changing applications clocks requires root, see
+    /// `Device.set_api_restricted()`.
+    pub restrict_application_clocks: Option<bool>,
+    /// Whether changing auto boosted clocks requires root, see
+    /// `Device.set_api_restricted()`.
+    pub restrict_auto_boosted_clocks: Option<bool>
+}
+
+/// When a `ConfigChange` takes effect.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Activation {
+    /// As soon as it is applied.
+    Immediate,
+    /// After the `Device` is reset or the machine is rebooted.
+    Reset
+}
+
+/// A single setting that differs from a `DesiredConfig`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConfigChange {
+    Persistent { current: bool, desired: bool },
+    ComputeMode { current: ComputeMode, desired: ComputeMode },
+    /// Also planned when the desired state is already pending, in which case
+    /// applying it only reports that a reset is needed.
+    Ecc { current: EccModeState, desired: bool },
+    /// In milliwatts.
+    PowerLimit { current: u32, desired: u32 },
+    ApplicationsClocks {
+        current: ApplicationsClocks,
+        desired: ApplicationsClocks
+    },
+    Accounting { current: bool, desired: bool },
+    ApiRestricted { api: Api, current: bool, desired: bool }
+}
+
+impl ConfigChange {
+    /// When this change takes effect.
+    pub fn activation(&self) -> Activation {
+        match *self {
+            ConfigChange::Ecc { .. } => Activation::Reset,
+            _ => Activation::Immediate,
+        }
+    }
+
+    fn apply(&self, device: &mut Device) -> Result<()> {
+        match *self {
+            ConfigChange::Persistent { desired, .. } => set_persistent(device, desired),
+            ConfigChange::ComputeMode { ref desired, .. } => device.set_compute_mode(desired.clone()),
+            ConfigChange::Ecc { ref current, desired } => {
+                if current.pending_enabled != desired {
+                    device.set_ecc(desired)
+                } else {
+                    Ok(())
+                }
+            },
+            ConfigChange::PowerLimit { desired, .. } => device.set_power_management_limit(desired),
+            ConfigChange::ApplicationsClocks { desired, .. } => {
+                device.set_applications_clocks(desired.memory, desired.graphics)
+            },
+            ConfigChange::Accounting { desired, .. } => device.set_accounting(desired),
+            ConfigChange::ApiRestricted { ref api, desired, .. } => {
+                device.set_api_restricted(api.clone(), desired)
+            },
+        }
+    }
+}
+
+impl fmt::Display for ConfigChange {
+    /// Writes the setting with its current and desired values, e.g.
+    /// `Compute Mode: Default -> Exclusive_Process`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigChange::Persistent { current, desired } => write!(
+                f,
+                "Persistence Mode: {} -> {}",
+                enabled_disabled(current),
+                enabled_disabled(desired)
+            ),
+            ConfigChange::ComputeMode {
+                ref current,
+                ref desired
+            } => write!(f, "Compute Mode: {} -> {}", current, desired),
+            ConfigChange::Ecc { ref current, desired } => {
+                write!(f, "ECC: {} -> {}", current, enabled_disabled(desired))
+            },
+            ConfigChange::PowerLimit { current, desired } => write!(
+                f,
+                "Power Limit: {:.2} W -> {:.2} W",
+                f64::from(current) / 1000.0,
+                f64::from(desired) / 1000.0
+            ),
+            ConfigChange::ApplicationsClocks { current, desired } => {
+                write!(f, "Applications Clocks: {} -> {}", current, desired)
+            },
+            ConfigChange::Accounting { current, desired } => write!(
+                f,
+                "Accounting: {} -> {}",
+                enabled_disabled(current),
+                enabled_disabled(desired)
+            ),
+            ConfigChange::ApiRestricted {
+                ref api,
+                current,
+                desired
+            } => write!(
+                f,
+                "{} API: {} -> {}",
+                api,
+                restricted_unrestricted(current),
+                restricted_unrestricted(desired)
+            ),
+        }?;
+
+        if self.activation() == Activation::Reset {
+            f.write_str(" (requires a reset)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returned from `DesiredConfig.plan()` and `DesiredConfig.apply()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConfigPlan {
+    /// Every setting that differs, in the order they are applied.
+    pub changes: Vec<ConfigChange>
+}
+
+impl ConfigPlan {
+    /// Whether the `Device` already has the desired configuration.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether any change only takes effect after a reset.
+    pub fn requires_reset(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.activation() == Activation::Reset)
+    }
+}
+
+impl fmt::Display for ConfigPlan {
+    /// Writes one change per line, or `No changes`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.changes.is_empty() {
+            return f.write_str("No changes");
+        }
+
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+// The current value of every setting a `DesiredConfig` sets.
+#[derive(Debug, Clone, Default)]
+struct Current {
+    persistent: Option<bool>,
+    compute_mode: Option<ComputeMode>,
+    ecc: Option<EccModeState>,
+    power_limit: Option<u32>,
+    applications_clocks: Option<ApplicationsClocks>,
+    accounting: Option<bool>,
+    restrict_application_clocks: Option<bool>,
+    restrict_auto_boosted_clocks: Option<bool>
+}
+
+impl DesiredConfig {
+    /**
+    Whether this config is meant for the given `Device`, according to
+    `.devices`.
+
+    # Errors
+
+    See `DeviceSelector.matches()`.
+    */
+    #[inline]
+    pub fn applies_to(&self, device: &Device) -> Result<bool> {
+        self.devices.matches(device)
+    }
+
+    /**
+    Compare this config against the given `Device`'s current state.
+
+    Only the settings this config sets are read.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` does not support one of the settings
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    pub fn plan(&self, device: &Device) -> Result<ConfigPlan> {
+        let mut current = Current::default();
+
+        if self.persistent.is_some() {
+            current.persistent = Some(persistent(device)?);
+        }
+        if self.compute_mode.is_some() {
+            current.compute_mode = Some(device.compute_mode()?);
+        }
+        if self.ecc.is_some() {
+            current.ecc = Some(device.is_ecc_enabled()?);
+        }
+        if self.power_limit.is_some() {
+            current.power_limit = Some(device.power_management_limit()?);
+        }
+        if self.applications_clocks.is_some() {
+            current.applications_clocks = Some(ApplicationsClocks {
+                memory: device.applications_clock(Clock::Memory)?,
+                graphics: device.applications_clock(Clock::Graphics)?
+            });
+        }
+        if self.accounting.is_some() {
+            current.accounting = Some(device.is_accounting_enabled()?);
+        }
+        if self.restrict_application_clocks.is_some() {
+            current.restrict_application_clocks = Some(device.is_api_restricted(Api::ApplicationClocks)?);
+        }
+        if self.restrict_auto_boosted_clocks.is_some() {
+            current.restrict_auto_boosted_clocks = Some(device.is_api_restricted(Api::AutoBoostedClocks)?);
+        }
+
+        Ok(self.diff(&current))
+    }
+
+    /**
+    Make the changes needed for the given `Device` to match this config.
+
+    Persistence mode is applied first, so that the settings after it survive
+    the driver unloading. Returns the changes that were made; check
+    `.requires_reset()` on it to find out whether the `Device` needs to be
+    reset for all of them to take effect.
+
+    Stops at the first change that fails, leaving the changes before it in
+    place. Call `.plan()` afterwards to find out what is left.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or a desired value is out of range
+    * `NotSupported`, if the `Device` does not support one of the settings
+    * `NoPermission`, if the user doesn't have permission to change a setting
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    pub fn apply(&self, device: &mut Device) -> Result<ConfigPlan> {
+        let plan = self.plan(device)?;
+
+        for change in &plan.changes {
+            change.apply(device)?;
+        }
+
+        Ok(plan)
+    }
+
+    fn diff(&self, current: &Current) -> ConfigPlan {
+        let mut changes = Vec::new();
+
+        if let (Some(desired), Some(current)) = (self.persistent, current.persistent) {
+            if desired != current {
+                changes.push(ConfigChange::Persistent { current, desired });
+            }
+        }
+
+        if let (Some(desired), Some(current)) = (self.compute_mode.as_ref(), current.compute_mode.as_ref()) {
+            if desired != current {
+                changes.push(ConfigChange::ComputeMode {
+                    current: current.clone(),
+                    desired: desired.clone()
+                });
+            }
+        }
+
+        if let (Some(desired), Some(current)) = (self.ecc, current.ecc.as_ref()) {
+            if current.currently_enabled != desired || current.pending_enabled != desired {
+                changes.push(ConfigChange::Ecc {
+                    current: current.clone(),
+                    desired
+                });
+            }
+        }
+
+        if let (Some(desired), Some(current)) = (self.power_limit, current.power_limit) {
+            if desired != current {
+                changes.push(ConfigChange::PowerLimit { current, desired });
+            }
+        }
+
+        if let (Some(desired), Some(current)) = (self.applications_clocks, current.applications_clocks) {
+            if desired != current {
+                changes.push(ConfigChange::ApplicationsClocks { current, desired });
+            }
+        }
+
+        if let (Some(desired), Some(current)) = (self.accounting, current.accounting) {
+            if desired != current {
+                changes.push(ConfigChange::Accounting { current, desired });
+            }
+        }
+
+        let restrictions = [
+            (
+                Api::ApplicationClocks,
+                self.restrict_application_clocks,
+                current.restrict_application_clocks
+            ),
+            (
+                Api::AutoBoostedClocks,
+                self.restrict_auto_boosted_clocks,
+                current.restrict_auto_boosted_clocks
+            ),
+        ];
+
+        for &(ref api, desired, current) in &restrictions {
+            if let (Some(desired), Some(current)) = (desired, current) {
+                if desired != current {
+                    changes.push(ConfigChange::ApiRestricted {
+                        api: api.clone(),
+                        current,
+                        desired
+                    });
+                }
+            }
+        }
+
+        ConfigPlan { changes }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn persistent(device: &Device) -> Result<bool> {
+    device.is_in_persistent_mode()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn persistent(_device: &Device) -> Result<bool> {
+    Err(ErrorKind::NotSupported.into())
+}
+
+#[cfg(target_os = "linux")]
+fn set_persistent(device: &mut Device, enabled: bool) -> Result<()> {
+    device.set_persistent(enabled)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_persistent(_device: &mut Device, _enabled: bool) -> Result<()> {
+    Err(ErrorKind::NotSupported.into())
+}
+
+fn restricted_unrestricted(value: bool) -> &'static str {
+    if value {
+        "Restricted"
+    } else {
+        "Unrestricted"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    fn current() -> Current {
+        Current {
+            persistent: Some(false),
+            compute_mode: Some(ComputeMode::Default),
+            ecc: Some(EccModeState {
+                currently_enabled: false,
+                pending_enabled: false
+            }),
+            power_limit: Some(250_000),
+            applications_clocks: Some(ApplicationsClocks {
+                memory: 877,
+                graphics: 1380
+            }),
+            accounting: Some(true),
+            restrict_application_clocks: Some(true),
+            restrict_auto_boosted_clocks: Some(true)
+        }
+    }
+
+    #[test]
+    fn only_differences_are_planned() {
+        let config = DesiredConfig {
+            persistent: Some(true),
+            compute_mode: Some(ComputeMode::ExclusiveProcess),
+            power_limit: Some(250_000),
+            accounting: Some(true),
+            restrict_application_clocks: Some(false),
+            ..DesiredConfig::default()
+        };
+        let plan = config.diff(&current());
+
+        assert!(!plan.requires_reset());
+        assert_eq!(
+            plan.to_string(),
+            "Persistence Mode: Disabled -> Enabled\n\
+             Compute Mode: Default -> Exclusive_Process\n\
+             Application Clocks API: Restricted -> Unrestricted"
+        );
+    }
+
+    #[test]
+    fn ecc_requires_reset() {
+        let config = DesiredConfig {
+            ecc: Some(true),
+            ..DesiredConfig::default()
+        };
+        let plan = config.diff(&current());
+
+        assert!(plan.requires_reset());
+        assert_eq!(plan.to_string(), "ECC: Disabled -> Enabled (requires a reset)");
+
+        // Already pending, so still planned until the reset happens
+        let mut pending = current();
+        pending.ecc = Some(EccModeState {
+            currently_enabled: false,
+            pending_enabled: true
+        });
+        assert!(config.diff(&pending).requires_reset());
+
+        pending.ecc = Some(EccModeState {
+            currently_enabled: true,
+            pending_enabled: true
+        });
+        assert!(config.diff(&pending).is_empty());
+    }
+
+    #[test]
+    fn empty_config_plans_nothing() {
+        let plan = DesiredConfig::default().diff(&current());
+
+        assert!(plan.is_empty());
+        assert_eq!(plan.to_string(), "No changes");
+    }
+
+    #[test]
+    fn plan() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let config = DesiredConfig {
+                compute_mode: Some(device.compute_mode()?),
+                ..DesiredConfig::default()
+            };
+
+            assert!(config.applies_to(device)?);
+            config.plan(device)
+        })
+    }
+}
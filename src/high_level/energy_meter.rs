@@ -0,0 +1,358 @@
+/*!
+Track how much energy a `Device` has consumed over time.
+
+Newer devices expose a total energy counter (`Device.total_energy_consumption()`)
+that is the most accurate source available. Older devices only expose power
+readings; for those, an `EnergyMeter` integrates the `Sampling::Power` samples
+that NVML keeps in its internal buffer.
+
+Either way, you call `.update()` periodically and get back the energy consumed
+since the previous update along with the running total:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::high_level::EnergyMeter;
+use std::thread;
+use std::time::Duration;
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+let mut meter = EnergyMeter::new(&device)?;
+
+loop {
+    thread::sleep(Duration::from_secs(5));
+
+    let reading = meter.update(&device)?;
+    println!(
+        "{:.2} J in the last interval, {:.2} J total",
+        reading.interval_joules,
+        reading.cumulative_joules
+    );
+}
+# }
+```
+
+Call `.update()` often enough that NVML's sample buffer does not overflow
+between calls when integrating samples; otherwise the missing time shows up
+as a gap (see `EnergyMeter.max_gap()`).
+*/
+
+use Device;
+use enum_wrappers::device::Sampling;
+use error::Result;
+use std::time::Duration;
+use struct_wrappers::device::Sample;
+use utils::optional;
+
+/// The source of energy data an `EnergyMeter` uses.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EnergySource {
+    /// `Device.total_energy_consumption()`. Supported on Volta and newer.
+    Counter,
+    /// `Device.samples(Sampling::Power, ..)`, integrated over time.
+    PowerSamples
+}
+
+/// Returned from `EnergyMeter.update()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EnergyReading {
+    /// Energy consumed since the previous update, in joules.
+    pub interval_joules: f64,
+    /// Energy consumed since this meter was created (or last reset), in
+    /// joules.
+    pub cumulative_joules: f64,
+    /// The number of discontinuities encountered during this interval.
+    ///
+    /// A discontinuity is either a reset of the energy counter (for instance
+    /// because the driver was reloaded) or a gap between power samples larger
+    /// than the meter's maximum gap. Energy consumed during a gap is not
+    /// counted, so a non-zero value here means the reading is a lower bound.
+    pub discontinuities: u32
+}
+
+/**
+Accumulates the energy consumed by a `Device`.
+
+The first call to `.update()` establishes a baseline and reports zero energy
+for the interval; subsequent calls report the energy consumed since the call
+before them.
+*/
+#[derive(Debug, Clone)]
+pub struct EnergyMeter {
+    source: EnergySource,
+    /// Maximum time between two power samples, in μs, that will be integrated
+    /// over.
+    max_gap: u64,
+    last_counter: Option<u64>,
+    /// Timestamp (μs) and value (mW) of the last power sample seen.
+    last_sample: Option<(u64, f64)>,
+    cumulative_joules: f64
+}
+
+impl EnergyMeter {
+    /**
+    Create a meter for the given `Device`, using the energy counter if the
+    `Device` supports it and falling back to integrating power samples
+    otherwise.
+
+    The `Device` is only queried to determine support; pass it (or another
+    handle to the same physical device) to `.update()` afterwards.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn new(device: &Device) -> Result<Self> {
+        Ok(match optional(device.total_energy_consumption())? {
+            Some(_) => Self::with_source(EnergySource::Counter),
+            None => Self::with_source(EnergySource::PowerSamples),
+        })
+    }
+
+    /// Create a meter that uses the given source regardless of device support.
+    #[inline]
+    pub fn with_source(source: EnergySource) -> Self {
+        EnergyMeter {
+            source,
+            max_gap: 1_000_000,
+            last_counter: None,
+            last_sample: None,
+            cumulative_joules: 0.0
+        }
+    }
+
+    /**
+    Set the largest gap between two consecutive power samples that will still
+    be integrated over. Defaults to one second.
+
+    Gaps larger than this (which occur if NVML's sample buffer overflowed
+    between two updates or if the device stopped reporting) are skipped and
+    counted as discontinuities rather than guessed at.
+
+    Has no effect when using `EnergySource::Counter`.
+    */
+    #[inline]
+    pub fn max_gap(mut self, gap: Duration) -> Self {
+        self.max_gap = gap.as_secs() * 1_000_000 + u64::from(gap.subsec_nanos() / 1000);
+        self
+    }
+
+    /// The source this meter gets its energy data from.
+    #[inline]
+    pub fn source(&self) -> &EnergySource {
+        &self.source
+    }
+
+    /// Energy consumed since this meter was created (or last reset), in joules.
+    #[inline]
+    pub fn cumulative_joules(&self) -> f64 {
+        self.cumulative_joules
+    }
+
+    /// Forget all previous state. The next update will establish a new baseline.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.last_counter = None;
+        self.last_sample = None;
+        self.cumulative_joules = 0.0;
+    }
+
+    /**
+    Query the given `Device` and accumulate the energy it consumed since the
+    last update.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if the `Device` does not support this meter's source
+    * `NotFound`, if using `EnergySource::PowerSamples` and sample entries are not found
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn update(&mut self, device: &Device) -> Result<EnergyReading> {
+        let (interval_joules, discontinuities) = match self.source {
+            EnergySource::Counter => {
+                let total = device.total_energy_consumption()?;
+                self.integrate_counter(total)
+            },
+            EnergySource::PowerSamples => {
+                let last_seen = self.last_sample.map(|(timestamp, _)| timestamp);
+                let samples = device.samples(Sampling::Power, last_seen)?;
+                self.integrate_samples(&samples)
+            },
+        };
+
+        self.cumulative_joules += interval_joules;
+
+        Ok(EnergyReading {
+            interval_joules,
+            cumulative_joules: self.cumulative_joules,
+            discontinuities
+        })
+    }
+
+    // Returns the joules consumed since the previous counter value and the
+    // number of discontinuities encountered.
+    fn integrate_counter(&mut self, total_mj: u64) -> (f64, u32) {
+        let result = match self.last_counter {
+            None => (0.0, 0),
+            // The counter went backwards, so it was reset (the driver was
+            // reloaded). Everything it holds now was consumed since the reset.
+            Some(last) if total_mj < last => (total_mj as f64 / 1000.0, 1),
+            Some(last) => ((total_mj - last) as f64 / 1000.0, 0),
+        };
+
+        self.last_counter = Some(total_mj);
+        result
+    }
+
+    // Integrates power samples using the trapezoidal rule. Returns the joules
+    // consumed and the number of gaps skipped.
+    fn integrate_samples(&mut self, samples: &[Sample]) -> (f64, u32) {
+        // The first batch holds whatever NVML had buffered before the meter
+        // existed; only the newest sample is kept as the baseline
+        if self.last_sample.is_none() {
+            self.last_sample = samples
+                .iter()
+                .filter_map(|sample| sample.value.as_f64().map(|mw| (sample.timestamp, mw)))
+                .max_by_key(|&(timestamp, _)| timestamp);
+
+            return (0.0, 0);
+        }
+
+        let mut joules = 0.0;
+        let mut gaps = 0;
+
+        for sample in samples {
+            // Values of a type this wrapper does not know can't be integrated
+            let milliwatts = match sample.value.as_f64() {
+                Some(milliwatts) => milliwatts,
+                None => continue,
+            };
+
+            match self.last_sample {
+                // Samples we've already seen (NVML treats the timestamp as
+                // inclusive on some drivers)
+                Some((last_ts, _)) if sample.timestamp <= last_ts => continue,
+                Some((last_ts, _)) if sample.timestamp - last_ts > self.max_gap => gaps += 1,
+                Some((last_ts, last_mw)) => {
+                    let seconds = (sample.timestamp - last_ts) as f64 / 1_000_000.0;
+                    // mW * s = mJ
+                    joules += (last_mw + milliwatts) / 2.0 * seconds / 1000.0;
+                },
+                None => {},
+            }
+
+            self.last_sample = Some((sample.timestamp, milliwatts));
+        }
+
+        (joules, gaps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use enums::device::SampleValue;
+    use test_utils::*;
+
+    fn sample(timestamp: u64, milliwatts: u32) -> Sample {
+        Sample {
+            timestamp,
+            value: SampleValue::U32(milliwatts)
+        }
+    }
+
+    #[test]
+    fn counter_deltas() {
+        let mut meter = EnergyMeter::with_source(EnergySource::Counter);
+
+        assert_eq!(meter.integrate_counter(10_000), (0.0, 0));
+        assert_eq!(meter.integrate_counter(15_000), (5.0, 0));
+    }
+
+    #[test]
+    fn counter_reset() {
+        let mut meter = EnergyMeter::with_source(EnergySource::Counter);
+
+        meter.integrate_counter(10_000);
+        assert_eq!(meter.integrate_counter(2_000), (2.0, 1));
+        assert_eq!(meter.integrate_counter(3_000), (1.0, 0));
+    }
+
+    #[test]
+    fn first_samples_are_baseline() {
+        let mut meter = EnergyMeter::with_source(EnergySource::PowerSamples);
+
+        // Everything NVML had buffered before the meter existed
+        assert_eq!(
+            meter.integrate_samples(&[sample(0, 100_000), sample(1_000_000, 100_000)]),
+            (0.0, 0)
+        );
+        assert_eq!(meter.integrate_samples(&[sample(2_000_000, 100_000)]), (100.0, 0));
+    }
+
+    #[test]
+    fn samples_trapezoidal() {
+        let mut meter = EnergyMeter::with_source(EnergySource::PowerSamples);
+        meter.integrate_samples(&[sample(0, 100_000)]);
+
+        // 100 W for half a second, then ramping to 200 W over the next half
+        let (joules, gaps) = meter.integrate_samples(&[
+            sample(0, 100_000),
+            sample(500_000, 100_000),
+            sample(1_000_000, 200_000)
+        ]);
+
+        assert_eq!(joules, 50.0 + 75.0);
+        assert_eq!(gaps, 0);
+
+        // Already-seen samples are skipped
+        let (joules, _) = meter.integrate_samples(&[
+            sample(1_000_000, 200_000),
+            sample(1_500_000, 200_000)
+        ]);
+
+        assert_eq!(joules, 100.0);
+    }
+
+    #[test]
+    fn samples_gap() {
+        let mut meter = EnergyMeter::with_source(EnergySource::PowerSamples)
+            .max_gap(Duration::from_millis(100));
+        meter.integrate_samples(&[sample(0, 100_000)]);
+
+        let (joules, gaps) = meter.integrate_samples(&[
+            sample(100_000, 100_000),
+            sample(5_000_000, 100_000),
+            sample(5_100_000, 100_000)
+        ]);
+
+        assert_eq!(joules, 20.0);
+        assert_eq!(gaps, 1);
+    }
+
+    #[test]
+    fn update() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mut meter = EnergyMeter::new(&device).expect("meter");
+
+        meter.update(&device).expect("first update");
+        meter.update(&device).expect("second update");
+    }
+}
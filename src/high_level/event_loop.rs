@@ -94,17 +94,17 @@ pub enum Event<'nvml> {
 
 impl<'nvml> From<EventData<'nvml>> for Event<'nvml> {
     fn from(struct_: EventData<'nvml>) -> Self {
-        if struct_.event_type.contains(EventTypes::CLOCK_CHANGE) {
+        if struct_.event_type.flags.contains(EventTypes::CLOCK_CHANGE) {
             Event::ClockChange(struct_.device)
-        } else if struct_.event_type.contains(EventTypes::CRITICAL_XID_ERROR) {
+        } else if struct_.event_type.flags.contains(EventTypes::CRITICAL_XID_ERROR) {
             // We can unwrap here because we know `event_data` will be `Some`
             // since the error is `CRITICAL_XID_ERROR`
             Event::CriticalXidError(struct_.device, struct_.event_data.unwrap())
-        } else if struct_.event_type.contains(EventTypes::DOUBLE_BIT_ECC_ERROR) {
+        } else if struct_.event_type.flags.contains(EventTypes::DOUBLE_BIT_ECC_ERROR) {
             Event::DoubleBitEccError(struct_.device)
-        } else if struct_.event_type.contains(EventTypes::PSTATE_CHANGE) {
+        } else if struct_.event_type.flags.contains(EventTypes::PSTATE_CHANGE) {
             Event::PowerStateChange(struct_.device)
-        } else if struct_.event_type.contains(EventTypes::SINGLE_BIT_ECC_ERROR) {
+        } else if struct_.event_type.flags.contains(EventTypes::SINGLE_BIT_ECC_ERROR) {
             Event::SingleBitEccError(struct_.device)
         } else {
             Event::Unknown
@@ -145,7 +145,7 @@ impl<'nvml> EventLoop<'nvml> {
     */
     #[inline]
     pub fn register_device(mut self, device: &'nvml Device<'nvml>) -> Result<Self> {
-        self.set = device.register_events(device.supported_event_types()?, self.set)?;
+        self.set = device.register_events(device.supported_event_types()?.flags, self.set)?;
 
         Ok(self)
     }
@@ -280,7 +280,7 @@ impl EventLoopProvider for NVML {
         let mut set = self.create_event_set()?;
 
         for d in devices {
-            set = d.register_events(d.supported_event_types()?, set)?;
+            set = d.register_events(d.supported_event_types()?.flags, set)?;
         }
 
         Ok(EventLoop {
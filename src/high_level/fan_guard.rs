@@ -0,0 +1,211 @@
+/*!
+Guard rails for manual fan control.
+
+Setting a fan speed with `Device.set_fan_speed()` takes the fan out of the
+driver's hands; if a script sets it too low and the GPU heats up, nothing
+will spin the fan back up. A `FanGuard` wraps a `Device` and refuses to set
+speeds below a configurable floor while the GPU is above a configurable
+temperature:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::high_level::FanGuard;
+
+let nvml = NVML::init()?;
+let mut device = nvml.device_by_index(0)?;
+
+// Never go below 40% while the GPU is hotter than 70 C
+let mut guard = FanGuard::new(&mut device, 40, 70);
+
+// Errors with `FanSpeedBelowFloor` if the GPU is above 70 C
+guard.set_all_fan_speeds(30)?;
+
+// Hand control back to the driver
+guard.restore_defaults()?;
+# Ok(())
+# }
+```
+*/
+
+use Device;
+use enum_wrappers::device::TemperatureSensor;
+use error::{ErrorKind, Result};
+
+/**
+Wraps a `Device` and refuses to set fan speeds below a floor while the GPU is
+hot.
+
+The temperature is read via `Device.temperature(TemperatureSensor::Gpu)`
+right before every speed change.
+*/
+#[derive(Debug)]
+pub struct FanGuard<'device, 'nvml: 'device> {
+    device: &'device mut Device<'nvml>,
+    min_speed: u32,
+    temperature_threshold: u32
+}
+
+impl<'device, 'nvml: 'device> FanGuard<'device, 'nvml> {
+    /**
+    Create a guard for the given `Device`.
+
+    Speeds below `min_speed` (a percentage of the maximum fan speed) will be
+    refused while the GPU temperature is above `temperature_threshold` (in °C).
+    */
+    #[inline]
+    pub fn new(device: &'device mut Device<'nvml>, min_speed: u32, temperature_threshold: u32) -> Self {
+        FanGuard {
+            device,
+            min_speed,
+            temperature_threshold
+        }
+    }
+
+    /// Obtain a reference to the `Device` this guard wraps.
+    #[inline]
+    pub fn device(&self) -> &Device<'nvml> {
+        self.device
+    }
+
+    /// The speed floor (a percentage of the maximum fan speed).
+    #[inline]
+    pub fn min_speed(&self) -> u32 {
+        self.min_speed
+    }
+
+    /// The temperature (in °C) above which the speed floor is enforced.
+    #[inline]
+    pub fn temperature_threshold(&self) -> u32 {
+        self.temperature_threshold
+    }
+
+    /**
+    Set the speed of the specified fan, refusing if `speed` is below the floor
+    while the GPU is above the temperature threshold.
+
+    # Errors
+
+    * `FanSpeedBelowFloor`, if the speed was refused
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid, `fan_idx` is invalid, or `speed` is
+      out of range
+    * `NotSupported`, if the `Device` does not have a fan or does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn set_fan_speed(&mut self, fan_idx: u32, speed: u32) -> Result<()> {
+        self.check(speed)?;
+        self.device.set_fan_speed(fan_idx, speed)
+    }
+
+    /**
+    Set the speed of every fan on the `Device`, refusing if `speed` is below the
+    floor while the GPU is above the temperature threshold.
+
+    The check happens once, before any fan is touched.
+
+    # Errors
+
+    * `FanSpeedBelowFloor`, if the speed was refused
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid or `speed` is out of range
+    * `NotSupported`, if the `Device` does not have a fan or does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn set_all_fan_speeds(&mut self, speed: u32) -> Result<()> {
+        self.check(speed)?;
+
+        for fan_idx in 0..self.device.num_fans()? {
+            self.device.set_fan_speed(fan_idx, speed)?;
+        }
+
+        Ok(())
+    }
+
+    /**
+    Restore every fan on the `Device` to its default, driver-controlled speed.
+
+    This is always allowed.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the device is invalid
+    * `NotSupported`, if the `Device` does not have a fan or does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn restore_defaults(&mut self) -> Result<()> {
+        for fan_idx in 0..self.device.num_fans()? {
+            self.device.set_default_fan_speed(fan_idx)?;
+        }
+
+        Ok(())
+    }
+
+    fn check(&self, speed: u32) -> Result<()> {
+        if speed >= self.min_speed {
+            return Ok(());
+        }
+
+        let temperature = self.device.temperature(TemperatureSensor::Gpu)?;
+        if temperature > self.temperature_threshold {
+            bail!(ErrorKind::FanSpeedBelowFloor(speed, self.min_speed, temperature));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use error::Error;
+    use test_utils::*;
+
+    #[test]
+    fn speed_above_floor_is_allowed() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+        let guard = FanGuard::new(&mut device, 40, 0);
+
+        guard.check(40).expect("allowed");
+    }
+
+    #[test]
+    fn speed_below_floor_is_refused_when_hot() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+        // Any GPU that is powered on will be above 0 C
+        let guard = FanGuard::new(&mut device, 40, 0);
+
+        match guard.check(39) {
+            Err(Error(ErrorKind::FanSpeedBelowFloor(39, 40, _), _)) => {},
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_all_fan_speeds() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+        let mut guard = FanGuard::new(&mut device, 40, 70);
+
+        guard.set_all_fan_speeds(60).expect("set to 60%");
+        guard.restore_defaults().expect("restored defaults");
+    }
+}
@@ -0,0 +1,708 @@
+/*!
+Evaluate a `Device`'s health, in the spirit of DCGM's health checks.
+
+A `HealthChecker` reads the signals NVML exposes for spotting failing or
+misbehaving hardware and combines them into a single verdict:
+
+* ECC error counts, from `Device.total_ecc_errors()`
+* retired pages and pending page retirement
+* row remapping failures and pending remappings
+* the infoROM checksum, from `Device.validate_info_rom()`
+* the temperature, against the `Device`'s slowdown and shutdown thresholds
+* the PCIe replay counter
+* the error counters of every active NvLink
+* the power draw, against the enforced power limit
+* critical XID errors, which have to be fed in from an event set
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::high_level::{HealthChecker, Verdict};
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+let checker = HealthChecker::new();
+
+let report = checker.check(&device)?;
+if report.verdict != Verdict::Healthy {
+    // e.g. "Critical: 2 volatile uncorrected ECC errors (limit 1)"
+    println!("{}", report);
+}
+# Ok(())
+# }
+```
+
+Checks the `Device` does not support are listed in `HealthReport.skipped`
+rather than causing an error.
+
+Thresholds are held in a `HealthThresholds`, which can be deserialized from a
+config file with the `serde` feature enabled. Fields missing from the config
+take their default values.
+*/
+
+use Device;
+use enum_wrappers::device::{EccCounter, MemoryError, RetirementCause, TemperatureSensor,
+                            TemperatureThreshold};
+use enum_wrappers::nv_link::ErrorCounter;
+use enums::event::XidError;
+use error::{Error, ErrorKind, Result};
+use high_level::nv_link_errors::ErrorCounts;
+use std::cmp::Reverse;
+use std::fmt;
+use structs::device::RemappedRowsInfo;
+use utils::optional;
+
+/// The outcome of a health check, from best to worst.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Verdict {
+    Healthy,
+    /// The `Device` works but should be looked at.
+    Warning,
+    /// The `Device` should be taken out of service.
+    Critical
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Verdict::Healthy => "Healthy",
+            Verdict::Warning => "Warning",
+            Verdict::Critical => "Critical",
+        })
+    }
+}
+
+/// The individual checks a `HealthChecker` runs.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HealthCheck {
+    Ecc,
+    RetiredPages,
+    RemappedRows,
+    InfoRom,
+    Temperature,
+    PcieReplays,
+    NvLink,
+    Power,
+    Xid
+}
+
+/// A limit with separate warning and critical levels.
+///
+/// A level of `None` is never reached. A value at or above a level reaches it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Limits<T> {
+    pub warning: Option<T>,
+    pub critical: Option<T>
+}
+
+impl<T: PartialOrd + Copy> Limits<T> {
+    /// Only warn, at `value`.
+    #[inline]
+    pub fn warning(value: T) -> Self {
+        Limits {
+            warning: Some(value),
+            critical: None
+        }
+    }
+
+    /// Only fail, at `value`.
+    #[inline]
+    pub fn critical(value: T) -> Self {
+        Limits {
+            warning: None,
+            critical: Some(value)
+        }
+    }
+
+    /// Never warn or fail.
+    #[inline]
+    pub fn none() -> Self {
+        Limits {
+            warning: None,
+            critical: None
+        }
+    }
+
+    /// The verdict for `value`, along with the level it reached.
+    pub fn evaluate(&self, value: T) -> Option<(Verdict, T)> {
+        match (self.critical, self.warning) {
+            (Some(critical), _) if value >= critical => Some((Verdict::Critical, critical)),
+            (_, Some(warning)) if value >= warning => Some((Verdict::Warning, warning)),
+            _ => None,
+        }
+    }
+}
+
+/**
+The thresholds a `HealthChecker` evaluates a `Device` against.
+
+The defaults follow DCGM's health checks: any uncorrected ECC error since the
+last driver load or 60 retired pages are critical, while a pending page
+retirement, a corrupted infoROM or running hot are warnings.
+*/
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HealthThresholds {
+    /// Corrected ECC errors since the last driver load.
+    pub volatile_corrected_ecc: Limits<u64>,
+    /// Uncorrected ECC errors since the last driver load.
+    pub volatile_uncorrected_ecc: Limits<u64>,
+    /// Corrected ECC errors over the `Device`'s lifetime.
+    pub aggregate_corrected_ecc: Limits<u64>,
+    /// Uncorrected ECC errors over the `Device`'s lifetime.
+    pub aggregate_uncorrected_ecc: Limits<u64>,
+    /// Pages retired for either cause.
+    pub retired_pages: Limits<u64>,
+    /// The verdict for pages pending retirement or rows pending remapping,
+    /// which take effect on the next reset.
+    pub pending_retirement: Verdict,
+    /// The verdict for a failed row remapping.
+    pub remapping_failure: Verdict,
+    /// The verdict for an infoROM with a bad checksum.
+    pub corrupted_info_rom: Verdict,
+    /**
+    How many degrees C below a temperature threshold to start reporting.
+
+    Reaching the slowdown threshold (minus this margin) is a warning; reaching
+    the shutdown threshold (minus this margin) is critical.
+    */
+    pub temperature_margin: u32,
+    /// The PCIe replay counter.
+    pub pcie_replays: Limits<u64>,
+    /// CRC flit and CRC data errors on a single NvLink.
+    pub nvlink_crc_errors: Limits<u64>,
+    /// Replay errors on a single NvLink.
+    pub nvlink_replay_errors: Limits<u64>,
+    /// Recovery errors on a single NvLink.
+    pub nvlink_recovery_errors: Limits<u64>,
+    /// The power draw as a fraction of the enforced power limit, e.g. `1.1`.
+    pub power: Limits<f64>,
+    /// XID errors that make a `Device` critical when recorded.
+    ///
+    /// Any other XID is a warning.
+    pub critical_xids: Vec<u64>
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        HealthThresholds {
+            volatile_corrected_ecc: Limits::none(),
+            volatile_uncorrected_ecc: Limits::critical(1),
+            aggregate_corrected_ecc: Limits::none(),
+            aggregate_uncorrected_ecc: Limits::warning(1),
+            retired_pages: Limits::critical(60),
+            pending_retirement: Verdict::Warning,
+            remapping_failure: Verdict::Critical,
+            corrupted_info_rom: Verdict::Warning,
+            temperature_margin: 0,
+            pcie_replays: Limits::warning(1000),
+            nvlink_crc_errors: Limits::warning(100),
+            nvlink_replay_errors: Limits::warning(100),
+            nvlink_recovery_errors: Limits::warning(1),
+            power: Limits::warning(1.1),
+            // Double bit ECC errors, row remapping failures, NvLink errors,
+            // fallen off the bus, contained and uncontained ECC errors, and
+            // GSP errors
+            critical_xids: vec![48, 63, 64, 74, 79, 94, 95, 119, 120]
+        }
+    }
+}
+
+/// A single problem found by a `HealthChecker`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Finding {
+    pub check: HealthCheck,
+    /// `Warning` or `Critical`.
+    pub verdict: Verdict,
+    /// A human-readable description of the problem.
+    pub reason: String
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.verdict, self.reason)
+    }
+}
+
+/// Returned from `HealthChecker.check()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HealthReport {
+    /// The worst verdict among the findings, or `Healthy` if there are none.
+    pub verdict: Verdict,
+    /// Every problem found, worst first.
+    pub findings: Vec<Finding>,
+    /// Checks that were not run because the `Device` does not support them.
+    pub skipped: Vec<HealthCheck>
+}
+
+impl fmt::Display for HealthReport {
+    /// Writes the findings separated by `; `, or `Healthy`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.findings.is_empty() {
+            return fmt::Display::fmt(&self.verdict, f);
+        }
+
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", finding)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Everything read from a `Device` for a check. `None` means not supported.
+#[derive(Debug, Clone, Default)]
+struct Readings {
+    // Volatile corrected, volatile uncorrected, aggregate corrected,
+    // aggregate uncorrected
+    ecc: [Option<u64>; 4],
+    retired_pages: Option<u64>,
+    pending_retirement: Option<bool>,
+    remapped_rows: Option<RemappedRowsInfo>,
+    info_rom_valid: Option<bool>,
+    // Current, slowdown and shutdown
+    temperature: Option<(u32, Option<u32>, Option<u32>)>,
+    pcie_replays: Option<u32>,
+    nvlinks: Option<Vec<(u32, ErrorCounts)>>,
+    // Usage and limit, in milliwatts
+    power: Option<(u32, u32)>
+}
+
+/**
+Evaluates a `Device`'s health against a set of `HealthThresholds`.
+
+XID errors are only reported through events, so they have to be recorded with
+`.record_xid()` as they arrive; they count towards every later check until
+`.clear_xids()` is called. Use one checker per `Device`.
+
+See the module docs for an example.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct HealthChecker {
+    thresholds: HealthThresholds,
+    xids: Vec<XidError>
+}
+
+impl HealthChecker {
+    /// Create a checker with the default thresholds.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a checker with the given thresholds.
+    #[inline]
+    pub fn with_thresholds(thresholds: HealthThresholds) -> Self {
+        HealthChecker {
+            thresholds,
+            xids: Vec::new()
+        }
+    }
+
+    /// The thresholds this checker evaluates against.
+    #[inline]
+    pub fn thresholds(&self) -> &HealthThresholds {
+        &self.thresholds
+    }
+
+    /// Record an XID error reported for this checker's `Device`, e.g. from
+    /// `EventData.event_data`.
+    #[inline]
+    pub fn record_xid(&mut self, xid: XidError) {
+        self.xids.push(xid);
+    }
+
+    /// Forget every recorded XID error, e.g. after the `Device` was reset.
+    #[inline]
+    pub fn clear_xids(&mut self) {
+        self.xids.clear();
+    }
+
+    /**
+    Read the given `Device`'s health signals and evaluate them.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all devices; checks a `Device` does not support are skipped.
+    */
+    pub fn check(&self, device: &Device) -> Result<HealthReport> {
+        let ecc = [
+            optional(device.total_ecc_errors(MemoryError::Corrected, EccCounter::Volatile))?,
+            optional(device.total_ecc_errors(MemoryError::Uncorrected, EccCounter::Volatile))?,
+            optional(device.total_ecc_errors(MemoryError::Corrected, EccCounter::Aggregate))?,
+            optional(device.total_ecc_errors(MemoryError::Uncorrected, EccCounter::Aggregate))?,
+        ];
+
+        let retired_pages = match (
+            optional(device.retired_pages(RetirementCause::MultipleSingleBitEccErrors))?,
+            optional(device.retired_pages(RetirementCause::DoubleBitEccError))?
+        ) {
+            (Some(single_bit), Some(double_bit)) => Some((single_bit.len() + double_bit.len()) as u64),
+            _ => None,
+        };
+
+        let info_rom_valid = match optional(device.validate_info_rom()) {
+            Ok(validated) => validated.map(|()| true),
+            Err(Error(ErrorKind::CorruptedInfoROM, _)) => Some(false),
+            Err(e) => return Err(e),
+        };
+
+        let temperature = match optional(device.temperature(TemperatureSensor::Gpu))? {
+            Some(current) => Some((
+                current,
+                optional(device.temperature_threshold(TemperatureThreshold::Slowdown))?,
+                optional(device.temperature_threshold(TemperatureThreshold::Shutdown))?
+            )),
+            None => None,
+        };
+
+        // Links that do not support every error counter are left out
+        let mut nvlinks = Vec::new();
+        for link in device.nvlinks()? {
+            if let (Some(replay), Some(recovery), Some(crc_flit), Some(crc_data)) = (
+                optional(link.error_counter(ErrorCounter::DlReplay))?,
+                optional(link.error_counter(ErrorCounter::DlRecovery))?,
+                optional(link.error_counter(ErrorCounter::DlCrcFlit))?,
+                optional(link.error_counter(ErrorCounter::DlCrcData))?
+            ) {
+                nvlinks.push((
+                    link.link(),
+                    ErrorCounts {
+                        replay,
+                        recovery,
+                        crc_flit,
+                        crc_data
+                    }
+                ));
+            }
+        }
+
+        let power = match optional(device.power_usage())? {
+            Some(usage) => optional(device.enforced_power_limit())?.map(|limit| (usage, limit)),
+            None => None,
+        };
+
+        Ok(self.evaluate(&Readings {
+            ecc,
+            retired_pages,
+            pending_retirement: optional(device.are_pages_pending_retired())?,
+            remapped_rows: optional(device.remapped_rows())?,
+            info_rom_valid,
+            temperature,
+            pcie_replays: optional(device.pcie_replay_counter())?,
+            nvlinks: if nvlinks.is_empty() { None } else { Some(nvlinks) },
+            power
+        }))
+    }
+
+    fn evaluate(&self, readings: &Readings) -> HealthReport {
+        let thresholds = &self.thresholds;
+        let mut findings = Vec::new();
+        let mut skipped = Vec::new();
+
+        let ecc_limits = [
+            (&thresholds.volatile_corrected_ecc, "volatile corrected ECC errors"),
+            (&thresholds.volatile_uncorrected_ecc, "volatile uncorrected ECC errors"),
+            (&thresholds.aggregate_corrected_ecc, "aggregate corrected ECC errors"),
+            (&thresholds.aggregate_uncorrected_ecc, "aggregate uncorrected ECC errors"),
+        ];
+
+        // Counters that are not supported are left out; the check is only
+        // skipped if none are
+        for (&(limits, what), count) in ecc_limits.iter().zip(readings.ecc.iter()) {
+            if let Some(count) = *count {
+                limit(&mut findings, HealthCheck::Ecc, limits, count, what);
+            }
+        }
+        if readings.ecc.iter().all(Option::is_none) {
+            skipped.push(HealthCheck::Ecc);
+        }
+
+        match (readings.retired_pages, readings.pending_retirement) {
+            (None, None) => skipped.push(HealthCheck::RetiredPages),
+            (retired, pending) => {
+                if let Some(retired) = retired {
+                    let limits = &thresholds.retired_pages;
+                    limit(&mut findings, HealthCheck::RetiredPages, limits, retired, "retired pages");
+                }
+                if pending == Some(true) {
+                    let verdict = thresholds.pending_retirement;
+                    find(&mut findings, HealthCheck::RetiredPages, verdict, "pages are pending retirement");
+                }
+            },
+        }
+
+        match readings.remapped_rows {
+            Some(ref rows) => {
+                if rows.failure_occurred {
+                    let verdict = thresholds.remapping_failure;
+                    find(&mut findings, HealthCheck::RemappedRows, verdict, "a row remapping failed");
+                }
+                if rows.is_pending {
+                    let verdict = thresholds.pending_retirement;
+                    find(&mut findings, HealthCheck::RemappedRows, verdict, "rows are pending remapping");
+                }
+            },
+            None => skipped.push(HealthCheck::RemappedRows),
+        }
+
+        match readings.info_rom_valid {
+            Some(true) => {},
+            Some(false) => {
+                let verdict = thresholds.corrupted_info_rom;
+                find(&mut findings, HealthCheck::InfoRom, verdict, "the infoROM is corrupted");
+            },
+            None => skipped.push(HealthCheck::InfoRom),
+        }
+
+        match readings.temperature {
+            Some((current, slowdown, shutdown)) => {
+                let reached = |threshold: Option<u32>| match threshold {
+                    Some(t) => current >= t.saturating_sub(thresholds.temperature_margin),
+                    None => false,
+                };
+
+                if reached(shutdown) {
+                    let reason = format!("{} C is near the shutdown threshold", current);
+                    find(&mut findings, HealthCheck::Temperature, Verdict::Critical, reason);
+                } else if reached(slowdown) {
+                    let reason = format!("{} C is near the slowdown threshold", current);
+                    find(&mut findings, HealthCheck::Temperature, Verdict::Warning, reason);
+                }
+            },
+            None => skipped.push(HealthCheck::Temperature),
+        }
+
+        match readings.pcie_replays {
+            Some(replays) => {
+                let limits = &thresholds.pcie_replays;
+                limit(&mut findings, HealthCheck::PcieReplays, limits, u64::from(replays), "PCIe replays");
+            },
+            None => skipped.push(HealthCheck::PcieReplays),
+        }
+
+        match readings.nvlinks {
+            Some(ref links) => for &(link, ref counts) in links {
+                let counters = [
+                    (&thresholds.nvlink_crc_errors, counts.crc_flit + counts.crc_data, "CRC errors"),
+                    (&thresholds.nvlink_replay_errors, counts.replay, "replay errors"),
+                    (&thresholds.nvlink_recovery_errors, counts.recovery, "recovery errors"),
+                ];
+
+                for &(limits, count, what) in &counters {
+                    let what = format!("{} on NvLink {}", what, link);
+                    limit(&mut findings, HealthCheck::NvLink, limits, count, &what);
+                }
+            },
+            None => skipped.push(HealthCheck::NvLink),
+        }
+
+        match readings.power {
+            Some((usage, power_limit)) if power_limit > 0 => {
+                let fraction = f64::from(usage) / f64::from(power_limit);
+
+                if let Some((verdict, _)) = thresholds.power.evaluate(fraction) {
+                    let reason = format!(
+                        "drawing {:.0}% of the {:.2} W power limit",
+                        fraction * 100.0,
+                        f64::from(power_limit) / 1000.0
+                    );
+                    find(&mut findings, HealthCheck::Power, verdict, reason);
+                }
+            },
+            _ => skipped.push(HealthCheck::Power),
+        }
+
+        for xid in &self.xids {
+            let verdict = match *xid {
+                XidError::Value(value) if thresholds.critical_xids.contains(&value) => Verdict::Critical,
+                _ => Verdict::Warning,
+            };
+
+            find(&mut findings, HealthCheck::Xid, verdict, format!("XID {}", xid));
+        }
+
+        // Stable, so findings of the same verdict stay in check order
+        findings.sort_by_key(|finding| Reverse(finding.verdict));
+
+        HealthReport {
+            verdict: findings.first().map_or(Verdict::Healthy, |finding| finding.verdict),
+            findings,
+            skipped
+        }
+    }
+}
+
+fn find<S: Into<String>>(findings: &mut Vec<Finding>, check: HealthCheck, verdict: Verdict, reason: S) {
+    if verdict != Verdict::Healthy {
+        findings.push(Finding {
+            check,
+            verdict,
+            reason: reason.into()
+        });
+    }
+}
+
+fn limit(findings: &mut Vec<Finding>, check: HealthCheck, limits: &Limits<u64>, value: u64, what: &str) {
+    if let Some((verdict, level)) = limits.evaluate(value) {
+        find(findings, check, verdict, format!("{} {} (limit {})", value, what, level));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    fn healthy() -> Readings {
+        Readings {
+            ecc: [Some(0); 4],
+            retired_pages: Some(0),
+            pending_retirement: Some(false),
+            remapped_rows: None,
+            info_rom_valid: Some(true),
+            temperature: Some((40, Some(90), Some(95))),
+            pcie_replays: Some(0),
+            nvlinks: None,
+            power: Some((100_000, 250_000))
+        }
+    }
+
+    #[test]
+    fn healthy_device() {
+        let report = HealthChecker::new().evaluate(&healthy());
+
+        assert_eq!(report.verdict, Verdict::Healthy);
+        assert!(report.findings.is_empty());
+        assert_eq!(report.skipped, vec![HealthCheck::RemappedRows, HealthCheck::NvLink]);
+        assert_eq!(report.to_string(), "Healthy");
+    }
+
+    #[test]
+    fn worst_finding_wins() {
+        let mut checker = HealthChecker::new();
+        checker.record_xid(XidError::Value(13));
+        checker.record_xid(XidError::Value(79));
+
+        let readings = Readings {
+            ecc: [Some(5), Some(2), Some(5), Some(2)],
+            pending_retirement: Some(true),
+            ..healthy()
+        };
+        let report = checker.evaluate(&readings);
+
+        assert_eq!(report.verdict, Verdict::Critical);
+        assert_eq!(
+            report.to_string(),
+            "Critical: 2 volatile uncorrected ECC errors (limit 1); Critical: XID 79; \
+             Warning: 2 aggregate uncorrected ECC errors (limit 1); \
+             Warning: pages are pending retirement; Warning: XID 13"
+        );
+
+        checker.clear_xids();
+        assert!(checker.evaluate(&healthy()).findings.is_empty());
+    }
+
+    #[test]
+    fn temperature_and_power() {
+        let readings = Readings {
+            temperature: Some((88, Some(90), Some(95))),
+            power: Some((260_000, 250_000)),
+            ..healthy()
+        };
+        assert!(HealthChecker::new().evaluate(&readings).findings.is_empty());
+
+        let checker = HealthChecker::with_thresholds(HealthThresholds {
+            temperature_margin: 5,
+            power: Limits::warning(1.0),
+            ..HealthThresholds::default()
+        });
+        let report = checker.evaluate(&readings);
+
+        assert_eq!(report.verdict, Verdict::Warning);
+        assert_eq!(
+            report.findings.iter().map(|finding| finding.check).collect::<Vec<_>>(),
+            vec![HealthCheck::Temperature, HealthCheck::Power]
+        );
+        assert_eq!(report.findings[1].reason, "drawing 104% of the 250.00 W power limit");
+    }
+
+    #[test]
+    fn unsupported_readings_are_skipped() {
+        let readings = Readings {
+            ecc: [None, Some(2), None, None],
+            temperature: None,
+            ..healthy()
+        };
+        let report = HealthChecker::new().evaluate(&readings);
+
+        assert_eq!(report.to_string(), "Critical: 2 volatile uncorrected ECC errors (limit 1)");
+        assert_eq!(
+            report.skipped,
+            vec![HealthCheck::RemappedRows, HealthCheck::Temperature, HealthCheck::NvLink]
+        );
+
+        let readings = Readings {
+            ecc: [None; 4],
+            ..healthy()
+        };
+        assert_eq!(HealthChecker::new().evaluate(&readings).skipped[0], HealthCheck::Ecc);
+    }
+
+    #[test]
+    fn nvlink_errors() {
+        let counts = ErrorCounts {
+            replay: 0,
+            recovery: 0,
+            crc_flit: 60,
+            crc_data: 40
+        };
+        let readings = Readings {
+            nvlinks: Some(vec![(0, ErrorCounts::default()), (3, counts)]),
+            ..healthy()
+        };
+        let report = HealthChecker::new().evaluate(&readings);
+
+        assert_eq!(report.to_string(), "Warning: 100 CRC errors on NvLink 3 (limit 100)");
+    }
+
+    #[test]
+    fn limits() {
+        let limits = Limits {
+            warning: Some(10),
+            critical: Some(20)
+        };
+
+        assert_eq!(limits.evaluate(9), None);
+        assert_eq!(limits.evaluate(10), Some((Verdict::Warning, 10)));
+        assert_eq!(limits.evaluate(25), Some((Verdict::Critical, 20)));
+        assert_eq!(Limits::none().evaluate(u64::max_value()), None);
+    }
+
+    #[test]
+    fn check() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| HealthChecker::new().check(device))
+    }
+}
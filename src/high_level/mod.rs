@@ -1,4 +1,30 @@
 #[cfg(target_os = "linux")]
+pub mod affinity;
+pub mod device_config;
+pub mod energy_meter;
+#[cfg(target_os = "linux")]
 pub mod event_loop;
+pub mod fan_guard;
+pub mod health;
+pub mod nv_link_bandwidth;
+pub mod nv_link_errors;
+#[cfg(target_os = "linux")]
+pub mod placement;
+pub mod sampler;
+pub mod throttle_tracker;
+#[cfg(target_os = "linux")]
+pub mod topology;
+
+pub use self::device_config::{Activation, ApplicationsClocks, ConfigChange, ConfigPlan, DesiredConfig, DeviceSelector};
+pub use self::energy_meter::{EnergyMeter, EnergyReading, EnergySource};
 #[cfg(target_os = "linux")]
 pub use self::event_loop::{Event, EventLoop, EventLoopProvider};
+pub use self::fan_guard::FanGuard;
+pub use self::health::{Finding, HealthCheck, HealthChecker, HealthReport, HealthThresholds, Limits, Verdict};
+pub use self::nv_link_bandwidth::{LinkThroughput, NvLinkBandwidth, NvLinkBandwidthMeter};
+pub use self::nv_link_errors::{ErrorCounts, LinkErrors, NvLinkErrorAlert, NvLinkErrorMonitor, NvLinkErrorReport};
+pub use self::sampler::{Metric, MetricStats, Sampler, SamplerBuilder, TimedSample};
+pub use self::throttle_tracker::{PolicyViolation, ReasonTime, ThrottleBreakdown, ThrottleTracker};
+#[cfg(target_os = "linux")]
+pub use self::topology::{NvLinkEdge, NvLinkEndpoint, NvLinkGraph, Topology, TopologyGpu, TopologyLink,
+                         TopologyProvider};
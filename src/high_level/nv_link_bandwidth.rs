@@ -0,0 +1,311 @@
+/*!
+Measure NvLink throughput from the NvLink utilization counters.
+
+An `NvLinkBandwidthMeter` configures one of the two utilization counters on
+every active NvLink of a `Device` to count bytes, then turns successive counter
+readings into receive and transmit rates:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::bitmasks::nv_link::PacketTypes;
+use nvml::enums::nv_link::Counter;
+use nvml::high_level::NvLinkBandwidthMeter;
+use std::thread;
+use std::time::Duration;
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+let mut meter = NvLinkBandwidthMeter::new(
+    &device,
+    Counter::Zero,
+    PacketTypes::READ | PacketTypes::WRITE
+)?;
+
+loop {
+    thread::sleep(Duration::from_secs(1));
+
+    let bandwidth = meter.update(&device)?;
+    println!(
+        "RX {:.1} MB/s, TX {:.1} MB/s",
+        bandwidth.rx_bytes_per_sec / 1e6,
+        bandwidth.tx_bytes_per_sec / 1e6
+    );
+}
+# }
+```
+
+Configuring the counter resets it, so avoid sharing a counter between meters
+(or other tools) on the same `Device`; use `Counter::Zero` for one and
+`Counter::One` for the other.
+*/
+
+use Device;
+use bitmasks::nv_link::PacketTypes;
+use enum_wrappers::nv_link::UtilizationCountUnit;
+use enums::nv_link::Counter;
+use error::Result;
+use std::time::{Duration, Instant};
+use struct_wrappers::nv_link::UtilizationControl;
+use structs::nv_link::UtilizationCounter;
+
+/// The throughput of a single NvLink over an interval.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinkThroughput {
+    /// The link's index on the `Device`.
+    pub link: u32,
+    /// Bytes received per second.
+    pub rx_bytes_per_sec: f64,
+    /// Bytes transmitted per second.
+    pub tx_bytes_per_sec: f64,
+    /// Whether either counter wrapped around during the interval.
+    ///
+    /// The rates account for the wraparound, but a counter that was reset by
+    /// something else during the interval looks the same and produces a bogus
+    /// rate.
+    pub wrapped: bool
+}
+
+/// Returned from `NvLinkBandwidthMeter.update()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NvLinkBandwidth {
+    /// The time since the previous update. Zero for the first update.
+    pub interval: Duration,
+    /// The throughput of each link, ordered by link index.
+    pub links: Vec<LinkThroughput>,
+    /// Bytes received per second across all links.
+    pub rx_bytes_per_sec: f64,
+    /// Bytes transmitted per second across all links.
+    pub tx_bytes_per_sec: f64
+}
+
+/**
+Turns NvLink utilization counter readings into throughput.
+
+The first call to `.update()` establishes a baseline and reports zero
+throughput; subsequent calls report the throughput since the call before them.
+*/
+#[derive(Debug, Clone)]
+pub struct NvLinkBandwidthMeter {
+    counter: Counter,
+    links: Vec<u32>,
+    last: Option<(Instant, Vec<(u32, UtilizationCounter)>)>
+}
+
+impl NvLinkBandwidthMeter {
+    /**
+    Create a meter for every active NvLink of the given `Device`.
+
+    `counter` on each link is configured to count bytes of the given
+    `packet_types` and reset.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` doesn't support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[inline]
+    pub fn new(device: &Device, counter: Counter, packet_types: PacketTypes) -> Result<Self> {
+        let settings = UtilizationControl {
+            units: UtilizationCountUnit::Bytes,
+            packet_filter: packet_types.into()
+        };
+
+        let mut links = Vec::new();
+        for mut link in device.nvlinks()? {
+            link.set_utilization_control(counter.clone(), settings.clone(), true)?;
+            links.push(link.link());
+        }
+
+        Ok(NvLinkBandwidthMeter {
+            counter,
+            links,
+            last: None
+        })
+    }
+
+    /// The indices of the links this meter measures.
+    #[inline]
+    pub fn links(&self) -> &[u32] {
+        &self.links
+    }
+
+    /// Forget the previous reading. The next update will establish a new
+    /// baseline.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /**
+    Read the counters of the given `Device` and report the throughput since the
+    last update.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn update(&mut self, device: &Device) -> Result<NvLinkBandwidth> {
+        let readings = self.links
+            .iter()
+            .map(|&link| {
+                let value = device.link_wrapper_for(link).utilization_counter(self.counter.clone())?;
+                Ok((link, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(self.record(Instant::now(), readings))
+    }
+
+    fn record(&mut self, now: Instant, readings: Vec<(u32, UtilizationCounter)>) -> NvLinkBandwidth {
+        let (interval, links): (Duration, Vec<LinkThroughput>) = match self.last {
+            Some((last_time, ref last_readings)) => {
+                let interval = now.duration_since(last_time);
+                let links = readings
+                    .iter()
+                    .map(|&(link, ref current)| {
+                        match last_readings.iter().find(|reading| reading.0 == link) {
+                            Some(previous) => throughput(link, &previous.1, current, interval),
+                            None => idle(link),
+                        }
+                    })
+                    .collect();
+
+                (interval, links)
+            },
+            None => (Duration::from_secs(0), readings.iter().map(|&(link, _)| idle(link)).collect()),
+        };
+
+        self.last = Some((now, readings));
+
+        NvLinkBandwidth {
+            interval,
+            rx_bytes_per_sec: links.iter().map(|l| l.rx_bytes_per_sec).sum(),
+            tx_bytes_per_sec: links.iter().map(|l| l.tx_bytes_per_sec).sum(),
+            links
+        }
+    }
+}
+
+fn idle(link: u32) -> LinkThroughput {
+    LinkThroughput {
+        link,
+        rx_bytes_per_sec: 0.0,
+        tx_bytes_per_sec: 0.0,
+        wrapped: false
+    }
+}
+
+fn throughput(
+    link: u32,
+    previous: &UtilizationCounter,
+    current: &UtilizationCounter,
+    interval: Duration,
+) -> LinkThroughput {
+    let seconds = interval.as_secs() as f64 + f64::from(interval.subsec_nanos()) / 1e9;
+    let (rx, rx_wrapped) = delta(previous.receive, current.receive);
+    let (tx, tx_wrapped) = delta(previous.send, current.send);
+
+    let rate = |bytes: u64| if seconds > 0.0 { bytes as f64 / seconds } else { 0.0 };
+
+    LinkThroughput {
+        link,
+        rx_bytes_per_sec: rate(rx),
+        tx_bytes_per_sec: rate(tx),
+        wrapped: rx_wrapped || tx_wrapped
+    }
+}
+
+// The increase from `previous` to `current`, and whether the counter wrapped.
+fn delta(previous: u64, current: u64) -> (u64, bool) {
+    (current.wrapping_sub(previous), current < previous)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    fn reading(link: u32, receive: u64, send: u64) -> (u32, UtilizationCounter) {
+        (link, UtilizationCounter { receive, send })
+    }
+
+    fn meter() -> NvLinkBandwidthMeter {
+        NvLinkBandwidthMeter {
+            counter: Counter::Zero,
+            links: vec![0, 1],
+            last: None
+        }
+    }
+
+    #[test]
+    fn wraparound() {
+        assert_eq!(delta(10, 15), (5, false));
+        assert_eq!(delta(u64::max_value() - 1, 3), (5, true));
+    }
+
+    #[test]
+    fn rates() {
+        let mut meter = meter();
+        let start = Instant::now();
+
+        let first = meter.record(start, vec![reading(0, 0, 0), reading(1, 100, 100)]);
+        assert_eq!(first.interval, Duration::from_secs(0));
+        assert_eq!(first.rx_bytes_per_sec, 0.0);
+
+        let second = meter.record(
+            start + Duration::from_secs(2),
+            vec![reading(0, 2_000, 1_000), reading(1, 100, 4_100)]
+        );
+
+        assert_eq!(second.interval, Duration::from_secs(2));
+        assert_eq!(second.links[0].rx_bytes_per_sec, 1_000.0);
+        assert_eq!(second.links[0].tx_bytes_per_sec, 500.0);
+        assert_eq!(second.links[1].rx_bytes_per_sec, 0.0);
+        assert_eq!(second.links[1].tx_bytes_per_sec, 2_000.0);
+        assert_eq!(second.rx_bytes_per_sec, 1_000.0);
+        assert_eq!(second.tx_bytes_per_sec, 2_500.0);
+    }
+
+    #[test]
+    fn wrapped_counter() {
+        let mut meter = meter();
+        let start = Instant::now();
+
+        meter.record(start, vec![reading(0, u64::max_value(), 0)]);
+        let bandwidth = meter.record(start + Duration::from_secs(1), vec![reading(0, 99, 0)]);
+
+        assert_eq!(bandwidth.links[0].rx_bytes_per_sec, 100.0);
+        assert!(bandwidth.links[0].wrapped);
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn update() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mut meter = NvLinkBandwidthMeter::new(&device, Counter::Zero, PacketTypes::all())
+            .expect("meter");
+
+        meter.update(&device).expect("first update");
+        meter.update(&device).expect("second update");
+    }
+}
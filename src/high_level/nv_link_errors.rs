@@ -0,0 +1,368 @@
+/*!
+Monitor NvLink error counters and raise alerts when they climb too quickly.
+
+`NvLink.error_counter()` reports raw totals. An `NvLinkErrorMonitor` snapshots
+every error counter of every active NvLink on a `Device`, computes how much each
+grew since the previous snapshot, and compares the growth rates against
+configurable thresholds:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::enum_wrappers::nv_link::ErrorCounter;
+use nvml::high_level::NvLinkErrorMonitor;
+use std::thread;
+use std::time::Duration;
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+let mut monitor = NvLinkErrorMonitor::new()
+    .threshold(ErrorCounter::DlCrcFlit, 10.0)
+    .threshold(ErrorCounter::DlCrcData, 10.0)
+    .threshold(ErrorCounter::DlRecovery, 1.0);
+
+loop {
+    thread::sleep(Duration::from_secs(60));
+
+    for alert in monitor.update(&device)?.alerts {
+        println!(
+            "link {}: {:?} at {:.1}/min (threshold {:.1}/min)",
+            alert.link, alert.counter, alert.per_minute, alert.threshold_per_minute
+        );
+    }
+}
+# }
+```
+*/
+
+use Device;
+use enum_wrappers::nv_link::ErrorCounter;
+use error::Result;
+use std::time::{Duration, Instant};
+
+/// The value of every NvLink error counter for a single link.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ErrorCounts {
+    /// Data link transmit replay errors.
+    pub replay: u64,
+    /// Data link transmit recovery errors.
+    pub recovery: u64,
+    /// Data link receive flow control digit CRC errors.
+    pub crc_flit: u64,
+    /// Data link receive data CRC errors.
+    pub crc_data: u64
+}
+
+impl ErrorCounts {
+    /// The value for the given counter.
+    #[inline]
+    pub fn get(&self, counter: &ErrorCounter) -> u64 {
+        match *counter {
+            ErrorCounter::DlReplay => self.replay,
+            ErrorCounter::DlRecovery => self.recovery,
+            ErrorCounter::DlCrcFlit => self.crc_flit,
+            ErrorCounter::DlCrcData => self.crc_data,
+            // Counters this wrapper does not know about are not captured
+            ErrorCounter::Unknown(_) => 0,
+        }
+    }
+
+    // The growth of each counter since `previous`. A counter that went
+    // backwards was reset, so everything it holds now is new.
+    fn since(&self, previous: &ErrorCounts) -> ErrorCounts {
+        let delta = |current: u64, previous: u64| {
+            if current < previous {
+                current
+            } else {
+                current - previous
+            }
+        };
+
+        ErrorCounts {
+            replay: delta(self.replay, previous.replay),
+            recovery: delta(self.recovery, previous.recovery),
+            crc_flit: delta(self.crc_flit, previous.crc_flit),
+            crc_data: delta(self.crc_data, previous.crc_data)
+        }
+    }
+}
+
+/// The error counters of a single link, as captured by `NvLinkErrorMonitor.update()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinkErrors {
+    /// The link's index on the `Device`.
+    pub link: u32,
+    /// The counter values at the time of capture (before any reset).
+    pub totals: ErrorCounts,
+    /// How much each counter grew since the previous capture. All zero for the
+    /// first capture of a link.
+    pub deltas: ErrorCounts
+}
+
+/// Raised when an error counter grows faster than its threshold.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NvLinkErrorAlert {
+    /// The link's index on the `Device`.
+    pub link: u32,
+    pub counter: ErrorCounter,
+    /// How much the counter grew since the previous capture.
+    pub delta: u64,
+    /// The counter's growth rate, in errors per minute.
+    pub per_minute: f64,
+    /// The threshold that was exceeded, in errors per minute.
+    pub threshold_per_minute: f64
+}
+
+/// Returned from `NvLinkErrorMonitor.update()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NvLinkErrorReport {
+    /// The time since the previous capture. Zero for the first capture.
+    pub interval: Duration,
+    /// The error counters of every active link, ordered by link index.
+    pub links: Vec<LinkErrors>,
+    /// Every threshold that was exceeded during the interval.
+    pub alerts: Vec<NvLinkErrorAlert>
+}
+
+/**
+Snapshots NvLink error counters and alerts on their growth rates.
+
+The first call to `.update()` establishes a baseline and never raises alerts;
+subsequent calls compare against the call before them.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct NvLinkErrorMonitor {
+    thresholds: Vec<(ErrorCounter, f64)>,
+    reset_after_capture: bool,
+    last: Option<(Instant, Vec<(u32, ErrorCounts)>)>
+}
+
+impl NvLinkErrorMonitor {
+    /// Create a monitor without any thresholds.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Raise an alert whenever `counter` grows by more than `per_minute` errors per
+    minute on any link.
+
+    Setting a threshold for a counter that already has one replaces it.
+    */
+    #[inline]
+    pub fn threshold(mut self, counter: ErrorCounter, per_minute: f64) -> Self {
+        self.thresholds.retain(|threshold| threshold.0 != counter);
+        self.thresholds.push((counter, per_minute));
+        self
+    }
+
+    /**
+    Whether to call `NvLink.reset_error_counters()` on every link after each
+    capture. Defaults to `false`.
+
+    Resetting requires root. With resets enabled, the totals reported by each
+    capture are the errors since the previous capture.
+    */
+    #[inline]
+    pub fn reset_after_capture(mut self, reset: bool) -> Self {
+        self.reset_after_capture = reset;
+        self
+    }
+
+    /**
+    Capture the error counters of every active NvLink on the given `Device` and
+    compare them against the previous capture.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` doesn't support this feature
+    * `NoPermission`, if resetting the counters is enabled and the user doesn't
+      have permission to do so
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[inline]
+    pub fn update(&mut self, device: &Device) -> Result<NvLinkErrorReport> {
+        let mut captures = Vec::new();
+
+        for mut link in device.nvlinks()? {
+            let counts = ErrorCounts {
+                replay: link.error_counter(ErrorCounter::DlReplay)?,
+                recovery: link.error_counter(ErrorCounter::DlRecovery)?,
+                crc_flit: link.error_counter(ErrorCounter::DlCrcFlit)?,
+                crc_data: link.error_counter(ErrorCounter::DlCrcData)?
+            };
+
+            if self.reset_after_capture {
+                link.reset_error_counters()?;
+            }
+
+            captures.push((link.link(), counts));
+        }
+
+        Ok(self.record(Instant::now(), captures))
+    }
+
+    fn record(&mut self, now: Instant, captures: Vec<(u32, ErrorCounts)>) -> NvLinkErrorReport {
+        let previous = self.last.take();
+        let interval = match previous {
+            Some((last_time, _)) => now.duration_since(last_time),
+            None => Duration::from_secs(0),
+        };
+        let minutes = (interval.as_secs() as f64 + f64::from(interval.subsec_nanos()) / 1e9) / 60.0;
+
+        let mut links = Vec::with_capacity(captures.len());
+        let mut alerts = Vec::new();
+
+        for &(link, ref totals) in &captures {
+            let last_counts = previous
+                .as_ref()
+                .and_then(|last| last.1.iter().find(|capture| capture.0 == link));
+
+            let deltas = match last_counts {
+                Some(last_counts) => totals.since(&last_counts.1),
+                None => ErrorCounts::default(),
+            };
+
+            if last_counts.is_some() && minutes > 0.0 {
+                for &(ref counter, threshold) in &self.thresholds {
+                    let delta = deltas.get(counter);
+                    let per_minute = delta as f64 / minutes;
+
+                    if per_minute > threshold {
+                        alerts.push(NvLinkErrorAlert {
+                            link,
+                            counter: counter.clone(),
+                            delta,
+                            per_minute,
+                            threshold_per_minute: threshold
+                        });
+                    }
+                }
+            }
+
+            links.push(LinkErrors {
+                link,
+                totals: totals.clone(),
+                deltas
+            });
+        }
+
+        // After a reset the next capture starts counting from zero
+        let baseline = if self.reset_after_capture {
+            captures.into_iter().map(|(link, _)| (link, ErrorCounts::default())).collect()
+        } else {
+            captures
+        };
+        self.last = Some((now, baseline));
+
+        NvLinkErrorReport {
+            interval,
+            links,
+            alerts
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    fn counts(replay: u64, recovery: u64, crc_flit: u64, crc_data: u64) -> ErrorCounts {
+        ErrorCounts {
+            replay,
+            recovery,
+            crc_flit,
+            crc_data
+        }
+    }
+
+    #[test]
+    fn deltas_and_alerts() {
+        let mut monitor = NvLinkErrorMonitor::new()
+            .threshold(ErrorCounter::DlCrcFlit, 10.0)
+            .threshold(ErrorCounter::DlRecovery, 1.0);
+        let start = Instant::now();
+
+        let first = monitor.record(start, vec![(0, counts(5, 5, 100, 0)), (1, counts(0, 0, 0, 0))]);
+        assert!(first.alerts.is_empty());
+        assert_eq!(first.links[0].deltas, ErrorCounts::default());
+
+        let second = monitor.record(
+            start + Duration::from_secs(120),
+            vec![(0, counts(5, 6, 130, 0)), (1, counts(0, 0, 10, 0))]
+        );
+
+        assert_eq!(second.links[0].deltas, counts(0, 1, 30, 0));
+        // Link 0: 15 CRC FLIT errors / min > 10; 0.5 recoveries / min <= 1
+        // Link 1: 5 CRC FLIT errors / min <= 10
+        assert_eq!(
+            second.alerts,
+            vec![NvLinkErrorAlert {
+                link: 0,
+                counter: ErrorCounter::DlCrcFlit,
+                delta: 30,
+                per_minute: 15.0,
+                threshold_per_minute: 10.0
+            }]
+        );
+    }
+
+    #[test]
+    fn counter_reset_by_someone_else() {
+        let mut monitor = NvLinkErrorMonitor::new();
+        let start = Instant::now();
+
+        monitor.record(start, vec![(0, counts(50, 0, 0, 0))]);
+        let report = monitor.record(start + Duration::from_secs(60), vec![(0, counts(3, 0, 0, 0))]);
+
+        assert_eq!(report.links[0].deltas.replay, 3);
+    }
+
+    #[test]
+    fn reset_after_capture_uses_zero_baseline() {
+        let mut monitor = NvLinkErrorMonitor::new().reset_after_capture(true);
+        let start = Instant::now();
+
+        monitor.record(start, vec![(0, counts(50, 0, 0, 0))]);
+        let report = monitor.record(start + Duration::from_secs(60), vec![(0, counts(4, 0, 0, 0))]);
+
+        assert_eq!(report.links[0].deltas.replay, 4);
+    }
+
+    #[test]
+    fn threshold_replaces() {
+        let monitor = NvLinkErrorMonitor::new()
+            .threshold(ErrorCounter::DlReplay, 1.0)
+            .threshold(ErrorCounter::DlReplay, 2.0);
+
+        assert_eq!(monitor.thresholds, vec![(ErrorCounter::DlReplay, 2.0)]);
+    }
+
+    #[test]
+    fn update() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mut monitor = NvLinkErrorMonitor::new().threshold(ErrorCounter::DlCrcData, 1.0);
+
+        monitor.update(&device).expect("first update");
+        monitor.update(&device).expect("second update");
+    }
+}
@@ -0,0 +1,331 @@
+/*!
+Topology-aware selection of GPU sets for job placement.
+
+Given a `Topology`, a list of GPUs that are free to use and the number of GPUs
+a job needs, `best_gpu_set()` picks the subset with the best interconnect:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+#
+# #[cfg(target_os = "linux")]
+# fn main() {
+#     example::actual_main().unwrap();
+# }
+#
+# #[cfg(target_os = "windows")]
+# fn main() {}
+#
+# #[cfg(target_os = "linux")]
+# mod example {
+# use nvml::NVML;
+# use nvml::error::Result;
+use nvml::high_level::TopologyProvider;
+use nvml::high_level::placement;
+#
+# pub fn actual_main() -> Result<()> {
+
+let nvml = NVML::init()?;
+let topology = nvml.topology()?;
+
+// GPUs 2 and 5 are busy
+let free = [0, 1, 3, 4, 6, 7];
+if let Some(placement) = placement::best_gpu_set(&topology, &free, 4) {
+    println!("Run on GPUs {:?}", placement.gpus);
+}
+# Ok(())
+# }
+# }
+```
+
+# Scoring
+
+Each pair of GPUs is given a score by `pair_score()`: every active NvLink
+between them is worth more than the best PCIe path, and PCIe paths are ranked by
+how far up the hierarchy their common ancestor is.
+
+Candidate subsets are then compared by, in order:
+
+1. The score of their worst-connected pair (collective operations run at the
+   speed of the slowest link), higher is better
+2. The sum of all their pair scores, higher is better
+3. The number of NUMA nodes they span, fewer is better
+4. Their GPU indices compared lexicographically, lower is better
+
+The last rule makes the result deterministic.
+
+Every subset of the requested size is considered, so this is intended for the
+handful of GPUs found in a single machine.
+*/
+
+use enum_wrappers::device::TopologyLevel;
+use high_level::topology::{Topology, TopologyLink};
+use std::cmp::Ordering;
+
+/// How much a single active NvLink adds to a pair's score. Larger than any
+/// PCIe score so that one NvLink outranks every PCIe path.
+const NVLINK_SCORE: u32 = 10;
+
+/// Returned from `best_gpu_set()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Placement {
+    /// The NVML indices of the selected GPUs, in ascending order.
+    pub gpus: Vec<u32>,
+    /// The score of the worst-connected pair. `None` if only one GPU was
+    /// selected.
+    pub min_pair_score: Option<u32>,
+    /// The sum of the scores of every pair.
+    pub total_pair_score: u32,
+    /// The number of distinct NUMA nodes the selected GPUs are attached to.
+    /// GPUs that do not report a NUMA node are not counted.
+    pub numa_nodes: usize
+}
+
+impl Placement {
+    // `Greater` means `self` is the better placement.
+    fn compare(&self, other: &Placement) -> Ordering {
+        self.min_pair_score
+            .cmp(&other.min_pair_score)
+            .then(self.total_pair_score.cmp(&other.total_pair_score))
+            .then(other.numa_nodes.cmp(&self.numa_nodes))
+            .then(other.gpus.cmp(&self.gpus))
+    }
+}
+
+/**
+Scores the connection between two GPUs. Higher is better.
+
+Each active NvLink is worth 10; on top of that, the PCIe path is worth 5 for
+GPUs on the same board or behind a single switch, 4 for multiple switches, 3 for
+a shared host bridge, 2 for a shared CPU and 1 otherwise.
+*/
+#[inline]
+pub fn pair_score(link: &TopologyLink) -> u32 {
+    let pcie = match link.level {
+        TopologyLevel::Internal | TopologyLevel::Single => 5,
+        TopologyLevel::Multiple => 4,
+        TopologyLevel::HostBridge => 3,
+        TopologyLevel::Cpu => 2,
+        TopologyLevel::System | TopologyLevel::Unknown(_) => 1,
+    };
+
+    link.nvlinks * NVLINK_SCORE + pcie
+}
+
+/**
+Selects the best-connected set of `count` GPUs out of those in `allowed`.
+
+`allowed` contains NVML indices; indices that do not appear in the `Topology`
+and duplicates are ignored. See the module docs for how sets are compared.
+
+Returns `None` if `count` is 0 or fewer than `count` GPUs are allowed.
+*/
+pub fn best_gpu_set(topology: &Topology, allowed: &[u32], count: usize) -> Option<Placement> {
+    // Positions within `topology.gpus`, ordered by NVML index
+    let mut candidates: Vec<usize> = (0..topology.gpus.len())
+        .filter(|&pos| allowed.contains(&topology.gpus[pos].index))
+        .collect();
+    candidates.sort_by_key(|&pos| topology.gpus[pos].index);
+
+    if count == 0 || candidates.len() < count {
+        return None;
+    }
+
+    let mut best: Option<Placement> = None;
+    let mut subset: Vec<usize> = (0..count).collect();
+
+    loop {
+        let positions: Vec<usize> = subset.iter().map(|&i| candidates[i]).collect();
+        let placement = score(topology, &positions);
+
+        let is_better = match best {
+            Some(ref best) => placement.compare(best) == Ordering::Greater,
+            None => true,
+        };
+        if is_better {
+            best = Some(placement);
+        }
+
+        if !next_combination(&mut subset, candidates.len()) {
+            break;
+        }
+    }
+
+    best
+}
+
+fn score(topology: &Topology, positions: &[usize]) -> Placement {
+    let mut min_pair_score = None;
+    let mut total_pair_score = 0;
+
+    for (i, &a) in positions.iter().enumerate() {
+        for &b in &positions[i + 1..] {
+            // Pairs missing from the matrix are treated as the worst case
+            let score = topology.link(a, b).map_or(0, pair_score);
+
+            total_pair_score += score;
+            min_pair_score = Some(match min_pair_score {
+                Some(min) if min < score => min,
+                _ => score,
+            });
+        }
+    }
+
+    let mut nodes: Vec<u32> = positions
+        .iter()
+        .filter_map(|&pos| topology.gpus[pos].numa_node)
+        .collect();
+    nodes.sort();
+    nodes.dedup();
+
+    let mut gpus: Vec<u32> = positions.iter().map(|&pos| topology.gpus[pos].index).collect();
+    gpus.sort();
+
+    Placement {
+        gpus,
+        min_pair_score,
+        total_pair_score,
+        numa_nodes: nodes.len()
+    }
+}
+
+// Advances `subset` (ascending indices into 0..n) to the next combination in
+// lexicographic order. Returns false once every combination has been visited.
+fn next_combination(subset: &mut [usize], n: usize) -> bool {
+    let k = subset.len();
+
+    for i in (0..k).rev() {
+        if subset[i] < n - k + i {
+            subset[i] += 1;
+            for j in i + 1..k {
+                subset[j] = subset[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use enum_wrappers::device::P2pStatus;
+    use high_level::topology::TopologyGpu;
+    use std::cmp;
+
+    // Builds a topology from a list of NUMA nodes and a function describing
+    // each pair's connection.
+    fn topology<F>(numa_nodes: &[u32], connection: F) -> Topology
+    where
+        F: Fn(usize, usize) -> (TopologyLevel, u32),
+    {
+        let gpus = numa_nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| TopologyGpu {
+                index: i as u32,
+                pci_bus_id: format!("00000000:{:02X}:00.0", i + 1),
+                cpu_affinity: None,
+                numa_node: Some(node)
+            })
+            .collect();
+
+        let n = numa_nodes.len();
+        let links = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| {
+                        if a == b {
+                            return None;
+                        }
+
+                        let (level, nvlinks) = connection(cmp::min(a, b), cmp::max(a, b));
+                        Some(TopologyLink {
+                            level,
+                            nvlinks,
+                            p2p_read: P2pStatus::Ok,
+                            p2p_write: P2pStatus::Ok
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Topology { gpus, links }
+    }
+
+    #[test]
+    fn prefers_nvlink() {
+        // 0-1 and 2-3 are NvLink pairs; everything else is PCIe on one switch
+        let topology = topology(&[0, 0, 0, 0], |a, b| match (a, b) {
+            (0, 1) | (2, 3) => (TopologyLevel::System, 2),
+            _ => (TopologyLevel::Single, 0),
+        });
+
+        let placement = best_gpu_set(&topology, &[0, 1, 2, 3], 2).expect("placement");
+        assert_eq!(placement.gpus, vec![0, 1]);
+
+        let placement = best_gpu_set(&topology, &[1, 2, 3], 2).expect("placement");
+        assert_eq!(placement.gpus, vec![2, 3]);
+    }
+
+    #[test]
+    fn prefers_weakest_link() {
+        // 0 has a fast NvLink to 1 but only PCIe to everything else, so every
+        // set containing 0 has a higher total but a weaker worst pair than
+        // {1, 2, 3}, which are all NvLinked
+        let topology = topology(&[0, 0, 0, 0], |a, b| match (a, b) {
+            (0, 1) => (TopologyLevel::System, 8),
+            (0, _) => (TopologyLevel::System, 0),
+            _ => (TopologyLevel::System, 1),
+        });
+
+        let placement = best_gpu_set(&topology, &[0, 1, 2, 3], 3).expect("placement");
+        assert_eq!(placement.gpus, vec![1, 2, 3]);
+        assert_eq!(placement.min_pair_score, Some(11));
+    }
+
+    #[test]
+    fn prefers_single_numa_node() {
+        let topology = topology(&[0, 1, 1, 0], |_, _| (TopologyLevel::System, 0));
+
+        let placement = best_gpu_set(&topology, &[0, 1, 2, 3], 2).expect("placement");
+        assert_eq!(placement.gpus, vec![0, 3]);
+        assert_eq!(placement.numa_nodes, 1);
+    }
+
+    #[test]
+    fn tie_break_is_lowest_indices() {
+        let topology = topology(&[0, 0, 0, 0], |_, _| (TopologyLevel::HostBridge, 0));
+
+        let placement = best_gpu_set(&topology, &[3, 2, 1], 2).expect("placement");
+        assert_eq!(placement.gpus, vec![1, 2]);
+
+        let placement = best_gpu_set(&topology, &[3], 1).expect("placement");
+        assert_eq!(placement.gpus, vec![3]);
+        assert_eq!(placement.min_pair_score, None);
+    }
+
+    #[test]
+    fn not_enough_gpus() {
+        let topology = topology(&[0, 0], |_, _| (TopologyLevel::Single, 0));
+
+        assert_eq!(best_gpu_set(&topology, &[0, 1, 7], 3), None);
+        assert_eq!(best_gpu_set(&topology, &[0, 1], 0), None);
+    }
+
+    #[test]
+    fn combinations() {
+        let mut subset = vec![0, 1];
+        let mut seen = vec![subset.clone()];
+        while next_combination(&mut subset, 4) {
+            seen.push(subset.clone());
+        }
+
+        assert_eq!(
+            seen,
+            vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3]]
+        );
+    }
+}
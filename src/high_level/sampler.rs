@@ -0,0 +1,567 @@
+/*!
+Poll device metrics on a background thread and keep a bounded history.
+
+A `Sampler` owns a thread that reads a set of `Metric`s from a set of devices at
+a fixed interval. Each reading is timestamped and stored in a per-device,
+per-metric ring buffer. Consumers query the history without touching NVML
+themselves:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::high_level::{Metric, Sampler};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+let nvml = Arc::new(NVML::init()?);
+let sampler = Sampler::builder()
+    .devices(vec![0, 1])
+    .metrics(vec![Metric::GpuUtilization, Metric::PowerUsage])
+    .interval(Duration::from_millis(500))
+    .capacity(1200)
+    .start(nvml)?;
+
+thread::sleep(Duration::from_secs(60));
+
+if let Some(stats) = sampler.stats(0, &Metric::PowerUsage, Duration::from_secs(60)) {
+    println!(
+        "power over the last minute: avg {:.0} mW, p99 {:.0} mW, max {:.0} mW",
+        stats.mean, stats.p99, stats.max
+    );
+}
+# Ok(())
+# }
+```
+
+The thread stops when the `Sampler` is dropped (or `.stop()` is called).
+
+Readings that fail (for example because a metric is not supported by a
+device) are not stored; `Sampler.errors()` counts them.
+*/
+
+use Device;
+use NVML;
+use enum_wrappers::device::{Clock, TemperatureSensor};
+use error::Result;
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// A value a `Sampler` can poll.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Metric {
+    /// `Device.utilization_rates().gpu`, in percent.
+    GpuUtilization,
+    /// `Device.utilization_rates().memory`, in percent.
+    MemoryUtilization,
+    /// `Device.power_usage()`, in milliwatts.
+    PowerUsage,
+    /// `Device.temperature(TemperatureSensor::Gpu)`, in °C.
+    Temperature,
+    /// `Device.memory_info().used`, in bytes.
+    MemoryUsed,
+    /// `Device.clock_info(Clock::Graphics)`, in MHz.
+    GraphicsClock,
+    /// `Device.clock_info(Clock::SM)`, in MHz.
+    SmClock,
+    /// `Device.clock_info(Clock::Memory)`, in MHz.
+    MemoryClock,
+    /// `Device.current_throttle_reasons()`, stored as the raw bitmask.
+    ///
+    /// Use `ThrottleReasons::from_bits_truncate(value as u64)` to decode a
+    /// sample. Statistics other than min/max are not meaningful for this
+    /// metric.
+    ThrottleReasons
+}
+
+impl Metric {
+    /// Every metric, in declaration order.
+    #[inline]
+    pub fn all() -> Vec<Metric> {
+        vec![
+            Metric::GpuUtilization,
+            Metric::MemoryUtilization,
+            Metric::PowerUsage,
+            Metric::Temperature,
+            Metric::MemoryUsed,
+            Metric::GraphicsClock,
+            Metric::SmClock,
+            Metric::MemoryClock,
+            Metric::ThrottleReasons,
+        ]
+    }
+
+    fn read(&self, device: &Device) -> Result<f64> {
+        Ok(match *self {
+            Metric::GpuUtilization => f64::from(device.utilization_rates()?.gpu),
+            Metric::MemoryUtilization => f64::from(device.utilization_rates()?.memory),
+            Metric::PowerUsage => f64::from(device.power_usage()?),
+            Metric::Temperature => f64::from(device.temperature(TemperatureSensor::Gpu)?),
+            Metric::MemoryUsed => device.memory_info()?.used as f64,
+            Metric::GraphicsClock => f64::from(device.clock_info(Clock::Graphics)?),
+            Metric::SmClock => f64::from(device.clock_info(Clock::SM)?),
+            Metric::MemoryClock => f64::from(device.clock_info(Clock::Memory)?),
+            Metric::ThrottleReasons => device.current_throttle_reasons()?.bits() as f64,
+        })
+    }
+}
+
+/// A single reading taken by a `Sampler`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedSample {
+    /// When the reading was taken.
+    pub timestamp: Instant,
+    pub value: f64
+}
+
+/// Summary statistics over a set of samples. Returned from `Sampler.stats()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MetricStats {
+    /// The number of samples summarized.
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// The median (nearest-rank).
+    pub p50: f64,
+    /// The 90th percentile (nearest-rank).
+    pub p90: f64,
+    /// The 99th percentile (nearest-rank).
+    pub p99: f64
+}
+
+impl MetricStats {
+    // `None` if `values` is empty.
+    fn from_values(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        sort(&mut values);
+        let sum: f64 = values.iter().sum();
+
+        Some(MetricStats {
+            count: values.len(),
+            min: values[0],
+            max: values[values.len() - 1],
+            mean: sum / values.len() as f64,
+            p50: nearest_rank(&values, 50.0),
+            p90: nearest_rank(&values, 90.0),
+            p99: nearest_rank(&values, 99.0)
+        })
+    }
+}
+
+/**
+Configures and starts a `Sampler`. Created with `Sampler::builder()`.
+
+Defaults to every device, every metric, a one second interval and 3600 samples
+of history per metric.
+*/
+#[derive(Debug, Clone)]
+pub struct SamplerBuilder {
+    devices: Option<Vec<u32>>,
+    metrics: Vec<Metric>,
+    interval: Duration,
+    capacity: usize
+}
+
+impl SamplerBuilder {
+    /// The NVML indices of the devices to poll.
+    #[inline]
+    pub fn devices(mut self, devices: Vec<u32>) -> Self {
+        self.devices = Some(devices);
+        self
+    }
+
+    /// The metrics to poll on each device.
+    #[inline]
+    pub fn metrics(mut self, metrics: Vec<Metric>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// The time between polls.
+    #[inline]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The number of samples kept per device and metric. Older samples are
+    /// discarded. Values below 1 are treated as 1.
+    #[inline]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /**
+    Spawn the sampling thread.
+
+    The first poll happens immediately.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if one of the device indices is invalid
+    * `GpuLost`, if one of the devices has fallen off the bus or is otherwise
+      inaccessible
+    * `Io`, if the thread could not be spawned
+    * `Unknown`, on any unexpected error
+    */
+    #[inline]
+    pub fn start(self, nvml: Arc<NVML>) -> Result<Sampler> {
+        let devices = match self.devices {
+            Some(devices) => devices,
+            None => (0..nvml.device_count()?).collect(),
+        };
+
+        // Surface bad indices here rather than as errors on the thread
+        for &index in &devices {
+            nvml.device_by_index(index)?;
+        }
+
+        let history = Arc::new(Mutex::new(History::new(self.capacity)));
+        let (stop, stopped) = mpsc::channel();
+
+        let thread_history = history.clone();
+        let metrics = self.metrics;
+        let interval = self.interval;
+        let thread_devices = devices.clone();
+
+        let handle = thread::Builder::new()
+            .name("nvml-sampler".into())
+            .spawn(move || {
+                let mut next = Instant::now();
+
+                loop {
+                    let handles: Vec<(u32, Result<Device>)> = thread_devices
+                        .iter()
+                        .map(|&index| (index, nvml.device_by_index(index)))
+                        .collect();
+
+                    let mut readings = Vec::with_capacity(handles.len() * metrics.len());
+                    for (index, device) in handles {
+                        for metric in &metrics {
+                            let value = match device {
+                                Ok(ref device) => metric.read(device).ok(),
+                                Err(_) => None,
+                            };
+                            readings.push((index, metric.clone(), value));
+                        }
+                    }
+
+                    let now = Instant::now();
+                    lock(&thread_history).record(now, readings);
+
+                    // Wait until the next tick rather than a full interval, so
+                    // the time spent polling does not push every tick back.
+                    // If polling took longer than an interval, start over
+                    // from now instead of firing the missed ticks at once.
+                    next += interval;
+                    let wait = if next > now {
+                        next - now
+                    } else {
+                        next = now;
+                        Duration::from_secs(0)
+                    };
+
+                    match stopped.recv_timeout(wait) {
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        _ => break,
+                    }
+                }
+            })?;
+
+        Ok(Sampler {
+            devices,
+            history,
+            stop: Some(stop),
+            handle: Some(handle)
+        })
+    }
+}
+
+/**
+Polls device metrics on a background thread.
+
+See the module docs for an example.
+*/
+#[derive(Debug)]
+pub struct Sampler {
+    devices: Vec<u32>,
+    history: Arc<Mutex<History>>,
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>
+}
+
+impl Sampler {
+    /// Configure a new `Sampler`.
+    #[inline]
+    pub fn builder() -> SamplerBuilder {
+        SamplerBuilder {
+            devices: None,
+            metrics: Metric::all(),
+            interval: Duration::from_secs(1),
+            capacity: 3600
+        }
+    }
+
+    /// The NVML indices of the devices being polled.
+    #[inline]
+    pub fn devices(&self) -> &[u32] {
+        &self.devices
+    }
+
+    /// The most recent sample of `metric` on `device`.
+    #[inline]
+    pub fn latest(&self, device: u32, metric: &Metric) -> Option<TimedSample> {
+        lock(&self.history)
+            .series(device, metric)
+            .and_then(|series| series.samples.back().cloned())
+    }
+
+    /// Every stored sample of `metric` on `device`, oldest first.
+    #[inline]
+    pub fn history(&self, device: u32, metric: &Metric) -> Vec<TimedSample> {
+        self.window(device, metric, None)
+    }
+
+    /// The samples of `metric` on `device` taken within the last `period`,
+    /// oldest first.
+    #[inline]
+    pub fn last(&self, device: u32, metric: &Metric, period: Duration) -> Vec<TimedSample> {
+        self.window(device, metric, Some(period))
+    }
+
+    /// Statistics over the samples of `metric` on `device` taken within the
+    /// last `period`. `None` if there are no such samples.
+    #[inline]
+    pub fn stats(&self, device: u32, metric: &Metric, period: Duration) -> Option<MetricStats> {
+        MetricStats::from_values(values(&self.last(device, metric, period)))
+    }
+
+    /**
+    The `p`th percentile (nearest-rank, `p` between 0 and 100) of the samples of
+    `metric` on `device` taken within the last `period`. `None` if there are
+    no such samples.
+    */
+    #[inline]
+    pub fn percentile(&self, device: u32, metric: &Metric, period: Duration, p: f64) -> Option<f64> {
+        let mut values = values(&self.last(device, metric, period));
+        if values.is_empty() {
+            return None;
+        }
+
+        sort(&mut values);
+        Some(nearest_rank(&values, p))
+    }
+
+    /// The number of failed readings of `metric` on `device`.
+    #[inline]
+    pub fn errors(&self, device: u32, metric: &Metric) -> u64 {
+        lock(&self.history)
+            .series(device, metric)
+            .map_or(0, |series| series.errors)
+    }
+
+    /// Stop the sampling thread and wait for it to exit. Stored samples remain
+    /// available.
+    #[inline]
+    pub fn stop(&mut self) {
+        // Dropping the sender wakes the thread
+        self.stop.take();
+
+        if let Some(handle) = self.handle.take() {
+            // The thread only panics if NVML does, nothing to recover
+            let _ = handle.join();
+        }
+    }
+
+    /// Whether the sampling thread is still running.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    fn window(&self, device: u32, metric: &Metric, period: Option<Duration>) -> Vec<TimedSample> {
+        let history = lock(&self.history);
+        let series = match history.series(device, metric) {
+            Some(series) => series,
+            None => return Vec::new(),
+        };
+
+        match period {
+            Some(period) => {
+                let now = Instant::now();
+                series
+                    .samples
+                    .iter()
+                    .filter(|sample| now.duration_since(sample.timestamp) <= period)
+                    .cloned()
+                    .collect()
+            },
+            None => series.samples.iter().cloned().collect(),
+        }
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[derive(Debug)]
+struct Series {
+    samples: VecDeque<TimedSample>,
+    errors: u64
+}
+
+#[derive(Debug)]
+struct History {
+    capacity: usize,
+    series: HashMap<(u32, Metric), Series>
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        History {
+            capacity: if capacity < 1 { 1 } else { capacity },
+            series: HashMap::new()
+        }
+    }
+
+    fn series(&self, device: u32, metric: &Metric) -> Option<&Series> {
+        self.series.get(&(device, metric.clone()))
+    }
+
+    fn record(&mut self, timestamp: Instant, readings: Vec<(u32, Metric, Option<f64>)>) {
+        let capacity = self.capacity;
+
+        for (device, metric, value) in readings {
+            let series = self.series.entry((device, metric)).or_insert_with(|| Series {
+                samples: VecDeque::with_capacity(capacity),
+                errors: 0
+            });
+
+            match value {
+                Some(value) => {
+                    if series.samples.len() == capacity {
+                        series.samples.pop_front();
+                    }
+                    series.samples.push_back(TimedSample { timestamp, value });
+                },
+                None => series.errors += 1,
+            }
+        }
+    }
+}
+
+// A poisoned lock only means the sampling thread panicked mid-update; the
+// history is still usable.
+fn lock<'a>(history: &'a Mutex<History>) -> MutexGuard<'a, History> {
+    history.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn values(samples: &[TimedSample]) -> Vec<f64> {
+    samples.iter().map(|sample| sample.value).collect()
+}
+
+fn sort(values: &mut [f64]) {
+    values.sort_by(|a, b| a.partial_cmp(b).expect("samples are never NaN"));
+}
+
+// `values` must be sorted and non-empty.
+fn nearest_rank(values: &[f64], p: f64) -> f64 {
+    let p = p.max(0.0).min(100.0);
+    let rank = (p / 100.0 * values.len() as f64).ceil() as usize;
+
+    values[cmp::max(rank, 1) - 1]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    #[test]
+    fn ring_buffer_is_bounded() {
+        let mut history = History::new(3);
+        let start = Instant::now();
+
+        for i in 0..5 {
+            history.record(
+                start + Duration::from_secs(i),
+                vec![(0, Metric::PowerUsage, Some(i as f64))]
+            );
+        }
+        history.record(start, vec![(0, Metric::PowerUsage, None)]);
+
+        let series = history.series(0, &Metric::PowerUsage).expect("series");
+        let values: Vec<f64> = series.samples.iter().map(|s| s.value).collect();
+
+        assert_eq!(values, vec![2.0, 3.0, 4.0]);
+        assert_eq!(series.errors, 1);
+        assert!(history.series(1, &Metric::PowerUsage).is_none());
+    }
+
+    #[test]
+    fn stats() {
+        let stats = MetricStats::from_values((1..101).rev().map(f64::from).collect())
+            .expect("stats");
+
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 100.0);
+        assert_eq!(stats.mean, 50.5);
+        assert_eq!(stats.p50, 50.0);
+        assert_eq!(stats.p90, 90.0);
+        assert_eq!(stats.p99, 99.0);
+
+        assert_eq!(MetricStats::from_values(Vec::new()), None);
+    }
+
+    #[test]
+    fn nearest_rank_bounds() {
+        let values = [10.0, 20.0, 30.0];
+
+        assert_eq!(nearest_rank(&values, 0.0), 10.0);
+        assert_eq!(nearest_rank(&values, 34.0), 20.0);
+        assert_eq!(nearest_rank(&values, 100.0), 30.0);
+        assert_eq!(nearest_rank(&values, 250.0), 30.0);
+    }
+
+    #[test]
+    fn sampler() {
+        let nvml = Arc::new(nvml());
+        let mut sampler = Sampler::builder()
+            .interval(Duration::from_millis(50))
+            .capacity(10)
+            .start(nvml)
+            .expect("started sampler");
+
+        thread::sleep(Duration::from_millis(200));
+        sampler.stop();
+
+        assert!(!sampler.is_running());
+        for &device in sampler.devices() {
+            for metric in Metric::all() {
+                let stored = sampler.history(device, &metric).len() as u64;
+                assert!(stored > 0 || sampler.errors(device, &metric) > 0);
+            }
+        }
+    }
+}
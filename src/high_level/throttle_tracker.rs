@@ -0,0 +1,409 @@
+/*!
+Attribute a `Device`'s time to the reasons its clocks were throttled.
+
+`Device.current_throttle_reasons()` only says why the clocks are being held
+down right now, and `Device.violation_status()` only counts the time spent
+throttled for a few performance policies. A `ThrottleTracker` samples both and
+builds a breakdown of how much of a window each reason was active for:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::high_level::ThrottleTracker;
+use std::thread;
+use std::time::Duration;
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+let mut tracker = ThrottleTracker::new();
+
+// Sample for the duration of a job
+for _ in 0..600 {
+    tracker.update(&device)?;
+    thread::sleep(Duration::from_millis(100));
+}
+tracker.update(&device)?;
+
+// e.g. "42% SW Power Cap, 10% HW Slowdown"
+println!("{}", tracker.breakdown());
+# Ok(())
+# }
+```
+
+Reasons this wrapper does not know about yet are reported by their bit, e.g.
+`Unknown (0x40)`, rather than being mistaken for the clocks not being
+throttled.
+
+Throttle reasons are sampled, so a reason that comes and goes between two
+updates will be missed; update at least a few times a second to get a useful
+picture. The time between two updates is attributed to the reasons seen at
+the earlier of the two.
+
+Use one tracker per device and job; call `.reset()` to reuse a tracker for the
+next job.
+*/
+
+use Device;
+use bitmasks::Lossless;
+use bitmasks::device::ThrottleReasons;
+use enum_wrappers::device::PerformancePolicy;
+use error::Result;
+use std::cmp::Reverse;
+use std::fmt;
+use std::time::{Duration, Instant};
+use struct_wrappers::device::ViolationTime;
+use utils::optional;
+
+// The reasons time is attributed to. `USER_DEFINED_CLOCKS` is an alias of
+// `APPLICATIONS_CLOCKS_SETTING`, and `NONE` is the absence of any.
+const REASONS: [ThrottleReasons; 6] = [
+    ThrottleReasons::GPU_IDLE,
+    ThrottleReasons::APPLICATIONS_CLOCKS_SETTING,
+    ThrottleReasons::SW_POWER_CAP,
+    ThrottleReasons::HW_SLOWDOWN,
+    ThrottleReasons::SYNC_BOOST,
+    ThrottleReasons::UNKNOWN
+];
+
+const POLICIES: [PerformancePolicy; 3] = [
+    PerformancePolicy::Power,
+    PerformancePolicy::Thermal,
+    PerformancePolicy::SyncBoost
+];
+
+/// The time a single throttle reason was active for.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReasonTime {
+    /// A single flag, or a single bit this wrapper does not know about.
+    pub reason: Lossless<ThrottleReasons>,
+    pub time: Duration,
+    /// `time` as a fraction (0.0 to 1.0) of the window.
+    pub fraction: f64
+}
+
+/// The time NVML reports a device was throttled for a performance policy.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PolicyViolation {
+    pub policy: PerformancePolicy,
+    /// The time spent throttled, according to `Device.violation_status()`.
+    pub time: Duration,
+    /// `time` as a fraction (0.0 to 1.0) of the time between the first and
+    /// last counter readings.
+    pub fraction: f64
+}
+
+/// Returned from `ThrottleTracker.breakdown()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThrottleBreakdown {
+    /// The time between the first and last update.
+    pub window: Duration,
+    /// The time during which the clocks were not throttled at all.
+    pub unthrottled: Duration,
+    /// Every reason that was active at some point during the window, most
+    /// time first. Reasons can overlap, so the fractions may sum to more
+    /// than 1.
+    pub reasons: Vec<ReasonTime>,
+    /// The violation counters of every policy the device supports.
+    pub policies: Vec<PolicyViolation>
+}
+
+impl ThrottleBreakdown {
+    /// The time the given reason was active for. Zero if it never was.
+    pub fn time<R: Into<Lossless<ThrottleReasons>>>(&self, reason: R) -> Duration {
+        let reason = reason.into();
+        self.reasons
+            .iter()
+            .find(|time| time.reason == reason)
+            .map_or_else(|| Duration::from_secs(0), |time| time.time)
+    }
+}
+
+impl fmt::Display for ThrottleBreakdown {
+    /// Writes each reason as a percentage of the window, e.g.
+    /// `42% SW Power Cap, 10% HW Slowdown`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.reasons.is_empty() {
+            return f.write_str("Not throttled");
+        }
+
+        for (i, time) in self.reasons.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:.0}% {}", time.fraction * 100.0, time.reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Samples a `Device`'s throttle reasons and violation counters over a window.
+
+See the module docs for an example.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleTracker {
+    last: Option<(Instant, Lossless<ThrottleReasons>)>,
+    window: Duration,
+    unthrottled: Duration,
+    reasons: Vec<(Lossless<ThrottleReasons>, Duration)>,
+    // The first and latest reading of each supported policy's counter
+    policies: Vec<(PerformancePolicy, ViolationTime, ViolationTime)>
+}
+
+impl ThrottleTracker {
+    /// Create a tracker with an empty window.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Sample the given `Device`'s current throttle reasons and violation
+    counters.
+
+    Policies the `Device` does not report violation counters for are left out
+    of the breakdown rather than causing an error.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+    */
+    pub fn update(&mut self, device: &Device) -> Result<()> {
+        let reasons = device.current_throttle_reasons()?;
+        let mut violations = Vec::with_capacity(POLICIES.len());
+
+        for policy in &POLICIES {
+            if let Some(violation) = optional(device.violation_status(policy.clone()))? {
+                violations.push((policy.clone(), violation));
+            }
+        }
+
+        self.record(Instant::now(), reasons, violations);
+        Ok(())
+    }
+
+    fn record(
+        &mut self,
+        now: Instant,
+        reasons: Lossless<ThrottleReasons>,
+        violations: Vec<(PerformancePolicy, ViolationTime)>
+    ) {
+        if let Some((then, active)) = self.last {
+            let elapsed = now.duration_since(then);
+            self.window += elapsed;
+
+            if active.bits() == 0 {
+                self.unthrottled += elapsed;
+            }
+
+            let known = REASONS
+                .iter()
+                .filter(|reason| active.flags.contains(**reason))
+                .map(|reason| Lossless::from(*reason));
+            let unknown = (0..64)
+                .map(|bit| 1u64 << bit)
+                .filter(|bit| active.unknown_bits & bit != 0)
+                .map(|bit| Lossless {
+                    flags: ThrottleReasons::empty(),
+                    unknown_bits: bit
+                });
+
+            for reason in known.chain(unknown) {
+                match self.reasons.iter_mut().find(|time| time.0 == reason) {
+                    Some(time) => time.1 += elapsed,
+                    None => self.reasons.push((reason, elapsed)),
+                }
+            }
+        }
+
+        for (policy, violation) in violations {
+            match self.policies.iter_mut().find(|counters| counters.0 == policy) {
+                Some(counters) => counters.2 = violation,
+                None => self.policies.push((policy, violation.clone(), violation)),
+            }
+        }
+
+        self.last = Some((now, reasons));
+    }
+
+    /// The breakdown of the window so far.
+    pub fn breakdown(&self) -> ThrottleBreakdown {
+        let window = duration_secs(self.window);
+        let fraction = |time: Duration| {
+            if window > 0.0 {
+                duration_secs(time) / window
+            } else {
+                0.0
+            }
+        };
+
+        let mut reasons: Vec<ReasonTime> = self.reasons
+            .iter()
+            .map(|time| ReasonTime {
+                reason: time.0,
+                time: time.1,
+                fraction: fraction(time.1)
+            })
+            .collect();
+        reasons.sort_by_key(|time| Reverse(time.time));
+
+        let policies = self.policies
+            .iter()
+            .map(|counters| {
+                let first = &counters.1;
+                let last = &counters.2;
+                // The counter is in ns, its reference time in μs
+                let violated = last.violation_time.saturating_sub(first.violation_time);
+                let elapsed = last.reference_time.saturating_sub(first.reference_time) * 1000;
+
+                PolicyViolation {
+                    policy: counters.0.clone(),
+                    time: Duration::new(violated / 1_000_000_000, (violated % 1_000_000_000) as u32),
+                    fraction: if elapsed > 0 {
+                        (violated as f64 / elapsed as f64).min(1.0)
+                    } else {
+                        0.0
+                    }
+                }
+            })
+            .collect();
+
+        ThrottleBreakdown {
+            window: self.window,
+            unthrottled: self.unthrottled,
+            reasons,
+            policies
+        }
+    }
+
+    /// Empty the window, e.g. at the start of a new job.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn duration_secs(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1e9
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    fn violation(reference_us: u64, violation_ns: u64) -> ViolationTime {
+        ViolationTime {
+            reference_time: reference_us,
+            violation_time: violation_ns
+        }
+    }
+
+    #[test]
+    fn attributes_time_to_previous_reasons() {
+        let mut tracker = ThrottleTracker::new();
+        let start = Instant::now();
+        let secs = Duration::from_secs;
+
+        tracker.record(start, ThrottleReasons::SW_POWER_CAP.into(), vec![]);
+        tracker.record(
+            start + secs(42),
+            (ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_SLOWDOWN).into(),
+            vec![]
+        );
+        tracker.record(start + secs(52), ThrottleReasons::NONE.into(), vec![]);
+        tracker.record(start + secs(100), ThrottleReasons::NONE.into(), vec![]);
+
+        let breakdown = tracker.breakdown();
+        assert_eq!(breakdown.window, secs(100));
+        assert_eq!(breakdown.unthrottled, secs(48));
+        assert_eq!(breakdown.time(ThrottleReasons::SW_POWER_CAP), secs(52));
+        assert_eq!(breakdown.time(ThrottleReasons::HW_SLOWDOWN), secs(10));
+        assert_eq!(breakdown.time(ThrottleReasons::GPU_IDLE), secs(0));
+        assert_eq!(breakdown.to_string(), "52% SW Power Cap, 10% HW Slowdown");
+    }
+
+    #[test]
+    fn policy_violations() {
+        let mut tracker = ThrottleTracker::new();
+        let start = Instant::now();
+
+        tracker.record(
+            start,
+            ThrottleReasons::NONE.into(),
+            vec![(PerformancePolicy::Power, violation(1_000_000, 5_000_000_000))]
+        );
+        tracker.record(
+            start + Duration::from_secs(10),
+            ThrottleReasons::NONE.into(),
+            vec![(PerformancePolicy::Power, violation(11_000_000, 7_500_000_000))]
+        );
+
+        let breakdown = tracker.breakdown();
+        assert_eq!(
+            breakdown.policies,
+            vec![PolicyViolation {
+                policy: PerformancePolicy::Power,
+                time: Duration::from_millis(2500),
+                fraction: 0.25
+            }]
+        );
+        assert_eq!(breakdown.to_string(), "Not throttled");
+    }
+
+    #[test]
+    fn unknown_reasons_are_not_unthrottled() {
+        let mut tracker = ThrottleTracker::new();
+        let start = Instant::now();
+
+        tracker.record(start, Lossless::from_bits(0x60), vec![]);
+        tracker.record(start + Duration::from_secs(10), ThrottleReasons::NONE.into(), vec![]);
+
+        let breakdown = tracker.breakdown();
+        assert_eq!(breakdown.window, Duration::from_secs(10));
+        assert_eq!(breakdown.unthrottled, Duration::from_secs(0));
+        assert_eq!(breakdown.time(Lossless::from_bits(0x20)), Duration::from_secs(10));
+        assert_eq!(breakdown.time(Lossless::from_bits(0x40)), Duration::from_secs(10));
+        assert_eq!(breakdown.to_string(), "100% Unknown (0x20), 100% Unknown (0x40)");
+    }
+
+    #[test]
+    fn reset() {
+        let mut tracker = ThrottleTracker::new();
+        let start = Instant::now();
+
+        tracker.record(start, ThrottleReasons::GPU_IDLE.into(), vec![]);
+        tracker.record(start + Duration::from_secs(1), ThrottleReasons::GPU_IDLE.into(), vec![]);
+        tracker.reset();
+
+        assert_eq!(tracker.breakdown().window, Duration::from_secs(0));
+        assert!(tracker.breakdown().reasons.is_empty());
+    }
+
+    #[test]
+    fn update() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mut tracker = ThrottleTracker::new();
+
+        tracker.update(&device).expect("first update");
+        tracker.update(&device).expect("second update");
+        tracker.breakdown();
+    }
+}
@@ -0,0 +1,514 @@
+/*!
+An aggregate view of how the GPUs in a system are connected, similar to
+`nvidia-smi topo -m`.
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+#
+# #[cfg(target_os = "linux")]
+# fn main() {
+#     example::actual_main().unwrap();
+# }
+#
+# #[cfg(target_os = "windows")]
+# fn main() {}
+#
+# #[cfg(target_os = "linux")]
+# mod example {
+# use nvml::NVML;
+# use nvml::error::Result;
+use nvml::high_level::TopologyProvider;
+#
+# pub fn actual_main() -> Result<()> {
+
+let nvml = NVML::init()?;
+let topology = nvml.topology()?;
+
+//         GPU0    GPU1    CPU Affinity    NUMA Affinity
+// GPU0     X      NV2     0-15            0
+// GPU1    NV2      X      0-15            0
+println!("{}", topology);
+
+if let Some(link) = topology.link(0, 1) {
+    println!("GPU0 <-> GPU1: {} NvLinks", link.nvlinks);
+}
+# Ok(())
+# }
+# }
+```
+*/
+
+use Device;
+use NVML;
+use enum_wrappers::device::{P2pCapabilitiesIndex, P2pStatus, TopologyLevel};
+use enum_wrappers::nv_link::RemoteDeviceType;
+use error::Result;
+use high_level::affinity;
+use std::fmt;
+use struct_wrappers::device::PciInfo;
+use structs::device::CpuSet;
+use utils::optional;
+
+/// Information about a single GPU in a `Topology`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyGpu {
+    /// The NVML index of the GPU.
+    pub index: u32,
+    /// The PCI bus ID of the GPU.
+    pub pci_bus_id: String,
+    /// The CPUs closest to the GPU. `None` if the GPU does not report this.
+    pub cpu_affinity: Option<CpuSet>,
+    /// The NUMA node the GPU is attached to. `None` if the GPU does not
+    /// report this.
+    pub numa_node: Option<u32>
+}
+
+/// How two GPUs in a `Topology` are connected.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopologyLink {
+    /// The closest common ancestor of the two GPUs in the PCIe hierarchy.
+    pub level: TopologyLevel,
+    /// The number of active NvLinks connecting the two GPUs directly.
+    pub nvlinks: u32,
+    /// Whether the GPUs can read from each other's memory.
+    pub p2p_read: P2pStatus,
+    /// Whether the GPUs can write to each other's memory.
+    pub p2p_write: P2pStatus
+}
+
+/**
+The connections between every pair of GPUs in the system.
+
+Obtain this via `NVML.topology()` (requires `TopologyProvider` to be in scope).
+Its `Display` implementation renders the familiar `nvidia-smi topo -m` table.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Topology {
+    /// The GPUs in the system, in NVML index order.
+    pub gpus: Vec<TopologyGpu>,
+    /// `links[a][b]` describes how `gpus[a]` and `gpus[b]` are connected. The
+    /// matrix is symmetric; entries on the diagonal are `None`.
+    pub links: Vec<Vec<Option<TopologyLink>>>
+}
+
+impl Topology {
+    /// How the GPUs at positions `a` and `b` in `.gpus` are connected.
+    ///
+    /// Returns `None` if `a == b` or either position is out of range.
+    #[inline]
+    pub fn link(&self, a: usize, b: usize) -> Option<&TopologyLink> {
+        self.links
+            .get(a)
+            .and_then(|row| row.get(b))
+            .and_then(|link| link.as_ref())
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<8}", "")?;
+        for gpu in &self.gpus {
+            write!(f, "{:<8}", format!("GPU{}", gpu.index))?;
+        }
+        writeln!(f, "CPU Affinity    NUMA Affinity")?;
+
+        for (a, gpu) in self.gpus.iter().enumerate() {
+            write!(f, "{:<8}", format!("GPU{}", gpu.index))?;
+
+            for b in 0..self.gpus.len() {
+                let cell = match self.link(a, b) {
+                    _ if a == b => " X".to_string(),
+                    Some(link) => link_label(link),
+                    None => "N/A".to_string(),
+                };
+                write!(f, "{:<8}", cell)?;
+            }
+
+            let cpus = match gpu.cpu_affinity {
+                Some(ref cpus) if !cpus.is_empty() => cpus.to_list_string(),
+                _ => "N/A".to_string(),
+            };
+            let node = match gpu.numa_node {
+                Some(node) => node.to_string(),
+                None => "N/A".to_string(),
+            };
+            writeln!(f, "{:<16}{}", cpus, node)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The label `nvidia-smi topo -m` uses for a connection.
+fn link_label(link: &TopologyLink) -> String {
+    if link.nvlinks > 0 {
+        return format!("NV{}", link.nvlinks);
+    }
+
+    match link.level {
+        // Boards such as the K80 connect their GPUs through an on-board PCIe
+        // switch, which nvidia-smi reports as a single bridge
+        TopologyLevel::Internal | TopologyLevel::Single => "PIX",
+        TopologyLevel::Multiple => "PXB",
+        TopologyLevel::HostBridge => "PHB",
+        TopologyLevel::Cpu => "NODE",
+        TopologyLevel::System => "SYS",
+        TopologyLevel::Unknown(_) => "?",
+    }.to_string()
+}
+
+/// The far end of an NvLink in an `NvLinkGraph`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NvLinkEndpoint {
+    /// A GPU visible to NVML, identified by its index.
+    Gpu(u32),
+    /// An NVSwitch, identified by its PCI bus ID.
+    Switch(String),
+    /// A CPU (the NPU of an IBM POWER system), identified by its PCI bus ID.
+    Cpu(String),
+    /// Anything else, identified by its PCI bus ID. This includes GPUs that are
+    /// not visible to NVML.
+    Unknown(String)
+}
+
+/// A single active NvLink in an `NvLinkGraph`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NvLinkEdge {
+    /// The NVML index of the GPU the link belongs to.
+    pub gpu: u32,
+    /// The link's index on that GPU.
+    pub link: u32,
+    /// What is on the other end of the link.
+    pub remote: NvLinkEndpoint,
+    /// The NvLink version the link is running at.
+    pub version: u32
+}
+
+/**
+Every active NvLink in the system and what is on the other end of each.
+
+Obtain this via `NVML.nvlink_graph()` (requires `TopologyProvider` to be in
+scope).
+
+Each physical link between two GPUs shows up twice in `.edges`, once from each
+side.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NvLinkGraph {
+    /// The NVML indices of every GPU in the system.
+    pub gpus: Vec<u32>,
+    /// Every active NvLink, ordered by GPU and then by link index.
+    pub edges: Vec<NvLinkEdge>
+}
+
+impl NvLinkGraph {
+    /// The active NvLinks of the given GPU.
+    #[inline]
+    pub fn edges_from(&self, gpu: u32) -> Vec<&NvLinkEdge> {
+        self.edges.iter().filter(|edge| edge.gpu == gpu).collect()
+    }
+
+    /// The number of active NvLinks directly connecting GPUs `a` and `b`.
+    #[inline]
+    pub fn links_between(&self, a: u32, b: u32) -> u32 {
+        self.edges
+            .iter()
+            .filter(|edge| edge.gpu == a && edge.remote == NvLinkEndpoint::Gpu(b))
+            .count() as u32
+    }
+
+    /**
+    Everything the given GPU is connected to, along with the number of active
+    NvLinks to each.
+
+    Endpoints are listed in the order their first link appears.
+    */
+    #[inline]
+    pub fn neighbors(&self, gpu: u32) -> Vec<(NvLinkEndpoint, u32)> {
+        let mut neighbors: Vec<(NvLinkEndpoint, u32)> = Vec::new();
+
+        for edge in self.edges_from(gpu) {
+            match neighbors.iter().position(|neighbor| neighbor.0 == edge.remote) {
+                Some(i) => neighbors[i].1 += 1,
+                None => neighbors.push((edge.remote.clone(), 1)),
+            }
+        }
+
+        neighbors
+    }
+}
+
+/// Adds methods to obtain a `Topology` and an `NvLinkGraph` to the `NVML`
+/// struct.
+///
+/// `use` it at your leisure.
+pub trait TopologyProvider {
+    fn topology(&self) -> Result<Topology>;
+    fn nvlink_graph(&self) -> Result<NvLinkGraph>;
+}
+
+impl TopologyProvider for NVML {
+    /**
+    Gather the connections between every pair of GPUs in the system.
+
+    Information a GPU does not support reporting (CPU affinity, NUMA node and
+    NvLinks) is left empty rather than failing the whole call.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if the OS does not support topology discovery
+    * `GpuLost`, if any `Device` has fallen off the bus or is otherwise
+      inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    fn topology(&self) -> Result<Topology> {
+        let count = self.device_count()?;
+        let devices = (0..count)
+            .map(|i| self.device_by_index(i))
+            .collect::<Result<Vec<Device>>>()?;
+
+        let mut gpus = Vec::with_capacity(devices.len());
+        let mut pci_addresses = Vec::with_capacity(devices.len());
+        for (index, device) in (0..count).zip(&devices) {
+            let pci_info = device.pci_info()?;
+            pci_addresses.push((pci_info.domain, pci_info.bus, pci_info.device));
+
+            gpus.push(TopologyGpu {
+                index,
+                pci_bus_id: pci_info.bus_id,
+                cpu_affinity: optional(affinity::ideal_cpu_set(device))?,
+                numa_node: optional(device.numa_node())?
+            });
+        }
+
+        let nvlinks = build_nvlink_graph(&devices, &pci_addresses)?;
+
+        let mut links = vec![vec![None; devices.len()]; devices.len()];
+        for a in 0..devices.len() {
+            for b in (a + 1)..devices.len() {
+                let link = TopologyLink {
+                    level: self.topology_common_ancestor(&devices[a], &devices[b])?,
+                    nvlinks: nvlinks.links_between(a as u32, b as u32),
+                    p2p_read: devices[a].p2p_status(&devices[b], P2pCapabilitiesIndex::Read)?,
+                    p2p_write: devices[a].p2p_status(&devices[b], P2pCapabilitiesIndex::Write)?
+                };
+
+                links[b][a] = Some(link.clone());
+                links[a][b] = Some(link);
+            }
+        }
+
+        Ok(Topology { gpus, links })
+    }
+
+    /**
+    Gather every active NvLink in the system and resolve what is on the other
+    end of each: another GPU, an NVSwitch or a CPU.
+
+    Links that do not support reporting their remote PCI info or version are
+    left out.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `GpuLost`, if any `Device` has fallen off the bus or is otherwise
+      inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    fn nvlink_graph(&self) -> Result<NvLinkGraph> {
+        let devices = (0..self.device_count()?)
+            .map(|i| self.device_by_index(i))
+            .collect::<Result<Vec<Device>>>()?;
+        let pci_addresses = devices
+            .iter()
+            .map(|device| device.pci_info().map(|info| (info.domain, info.bus, info.device)))
+            .collect::<Result<Vec<_>>>()?;
+
+        build_nvlink_graph(&devices, &pci_addresses)
+    }
+}
+
+// Walks every active NvLink of every device and resolves what is on the other
+// end.
+fn build_nvlink_graph(devices: &[Device], pci_addresses: &[(u32, u32, u32)]) -> Result<NvLinkGraph> {
+    let mut edges = Vec::new();
+
+    for (gpu, device) in (0..).zip(devices) {
+        for link in device.nvlinks()? {
+            // Links that cannot say where they lead are left out of the graph
+            let remote_pci = match optional(link.remote_pci_info())? {
+                Some(remote_pci) => remote_pci,
+                None => continue,
+            };
+            let version = match optional(link.version())? {
+                Some(version) => version,
+                None => continue,
+            };
+            let remote_type = optional(link.remote_device_type())?;
+
+            edges.push(NvLinkEdge {
+                gpu,
+                link: link.link(),
+                remote: resolve_endpoint(remote_type, &remote_pci, pci_addresses),
+                version
+            });
+        }
+    }
+
+    Ok(NvLinkGraph {
+        gpus: (0..devices.len() as u32).collect(),
+        edges
+    })
+}
+
+// Drivers that cannot report the remote device type are treated as if the
+// remote were a GPU; it is only reported as one if its address matches.
+fn resolve_endpoint(
+    remote_type: Option<RemoteDeviceType>,
+    remote_pci: &PciInfo,
+    pci_addresses: &[(u32, u32, u32)],
+) -> NvLinkEndpoint {
+    let address = (remote_pci.domain, remote_pci.bus, remote_pci.device);
+    let bus_id = remote_pci.bus_id.clone();
+
+    match remote_type.unwrap_or(RemoteDeviceType::Gpu) {
+        RemoteDeviceType::Gpu => match pci_addresses.iter().position(|&a| a == address) {
+            Some(index) => NvLinkEndpoint::Gpu(index as u32),
+            None => NvLinkEndpoint::Unknown(bus_id),
+        },
+        RemoteDeviceType::Switch => NvLinkEndpoint::Switch(bus_id),
+        RemoteDeviceType::IbmNpu => NvLinkEndpoint::Cpu(bus_id),
+        RemoteDeviceType::Unknown(_) => NvLinkEndpoint::Unknown(bus_id),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    fn gpu(index: u32) -> TopologyGpu {
+        TopologyGpu {
+            index,
+            pci_bus_id: format!("00000000:{:02X}:00.0", index + 1),
+            cpu_affinity: Some(CpuSet::from_cpus(0..16)),
+            numa_node: Some(0)
+        }
+    }
+
+    fn link(level: TopologyLevel, nvlinks: u32) -> Option<TopologyLink> {
+        Some(TopologyLink {
+            level,
+            nvlinks,
+            p2p_read: P2pStatus::Ok,
+            p2p_write: P2pStatus::Ok
+        })
+    }
+
+    #[test]
+    fn render() {
+        let topology = Topology {
+            gpus: vec![gpu(0), gpu(1), gpu(2)],
+            links: vec![
+                vec![None, link(TopologyLevel::Single, 2), link(TopologyLevel::System, 0)],
+                vec![link(TopologyLevel::Single, 2), None, link(TopologyLevel::HostBridge, 0)],
+                vec![link(TopologyLevel::System, 0), link(TopologyLevel::HostBridge, 0), None],
+            ]
+        };
+
+        let expected = concat!(
+            "        GPU0    GPU1    GPU2    CPU Affinity    NUMA Affinity\n",
+            "GPU0     X      NV2     SYS     0-15            0\n",
+            "GPU1    NV2      X      PHB     0-15            0\n",
+            "GPU2    SYS     PHB      X      0-15            0\n"
+        );
+        assert_eq!(topology.to_string(), expected);
+    }
+
+    #[test]
+    fn topology() {
+        let nvml = nvml();
+        test(3, || nvml.topology())
+    }
+
+    fn pci_info(bus: u32) -> PciInfo {
+        PciInfo {
+            bus,
+            bus_id: format!("00000000:{:02X}:00.0", bus),
+            device: 0,
+            domain: 0,
+            pci_device_id: 0,
+            pci_sub_system_id: None
+        }
+    }
+
+    #[test]
+    fn resolve_endpoints() {
+        let addresses = [(0, 1, 0), (0, 2, 0)];
+
+        assert_eq!(
+            resolve_endpoint(Some(RemoteDeviceType::Gpu), &pci_info(2), &addresses),
+            NvLinkEndpoint::Gpu(1)
+        );
+        assert_eq!(
+            resolve_endpoint(None, &pci_info(1), &addresses),
+            NvLinkEndpoint::Gpu(0)
+        );
+        assert_eq!(
+            resolve_endpoint(Some(RemoteDeviceType::Gpu), &pci_info(9), &addresses),
+            NvLinkEndpoint::Unknown("00000000:09:00.0".into())
+        );
+        assert_eq!(
+            resolve_endpoint(Some(RemoteDeviceType::Switch), &pci_info(2), &addresses),
+            NvLinkEndpoint::Switch("00000000:02:00.0".into())
+        );
+        assert_eq!(
+            resolve_endpoint(Some(RemoteDeviceType::IbmNpu), &pci_info(4), &addresses),
+            NvLinkEndpoint::Cpu("00000000:04:00.0".into())
+        );
+    }
+
+    #[test]
+    fn graph_queries() {
+        let edge = |gpu, link, remote| NvLinkEdge {
+            gpu,
+            link,
+            remote,
+            version: 3
+        };
+        let switch = NvLinkEndpoint::Switch("00000000:C0:00.0".into());
+        let graph = NvLinkGraph {
+            gpus: vec![0, 1],
+            edges: vec![
+                edge(0, 0, NvLinkEndpoint::Gpu(1)),
+                edge(0, 1, NvLinkEndpoint::Gpu(1)),
+                edge(0, 2, switch.clone()),
+                edge(1, 0, NvLinkEndpoint::Gpu(0)),
+                edge(1, 1, NvLinkEndpoint::Gpu(0)),
+            ]
+        };
+
+        assert_eq!(graph.links_between(0, 1), 2);
+        assert_eq!(graph.links_between(1, 0), 2);
+        assert_eq!(graph.edges_from(0).len(), 3);
+        assert_eq!(
+            graph.neighbors(0),
+            vec![(NvLinkEndpoint::Gpu(1), 2), (switch, 1)]
+        );
+    }
+
+    #[test]
+    fn nvlink_graph() {
+        let nvml = nvml();
+        test(3, || nvml.nvlink_graph())
+    }
+}
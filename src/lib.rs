@@ -111,12 +111,15 @@ for every NVML data structure.
 extern crate error_chain;
 #[macro_use]
 extern crate bitflags;
-#[macro_use]
-extern crate wrapcenum_derive;
 #[cfg(feature = "serde")]
 #[macro_use]
 extern crate serde;
 extern crate nvml_wrapper_sys as ffi;
+#[cfg(target_os = "linux")]
+extern crate libc;
+
+#[macro_use]
+mod macros;
 
 pub mod device;
 pub mod error;
@@ -128,7 +131,10 @@ pub mod enum_wrappers;
 pub mod event;
 pub mod bitmasks;
 pub mod nv_link;
+pub mod sample_cursor;
+pub mod units;
 pub mod high_level;
+mod utils;
 #[cfg(test)]
 mod test_utils;
 
@@ -136,6 +142,7 @@ mod test_utils;
 pub use device::Device;
 pub use event::EventSet;
 pub use nv_link::NvLink;
+pub use sample_cursor::SampleCursor;
 pub use unit::Unit;
 
 #[cfg(target_os = "linux")]
@@ -152,6 +159,7 @@ use std::ptr;
 #[cfg(target_os = "linux")]
 use struct_wrappers::device::PciInfo;
 use struct_wrappers::unit::HwbcEntry;
+use structs::nvml::CudaDriverVersion;
 
 /**
 The main struct that this library revolves around.
@@ -310,6 +318,28 @@ impl NVML {
         }
     }
 
+    /**
+    Gets the version of the system's CUDA driver.
+
+    This is the version of the CUDA driver API (`libcuda.so` / `nvcuda.dll`), which
+    is also the newest CUDA toolkit version the installed driver supports.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    #[inline]
+    pub fn sys_cuda_driver_version(&self) -> Result<CudaDriverVersion> {
+        unsafe {
+            let mut version: c_int = mem::zeroed();
+            nvml_try(nvmlSystemGetCudaDriverVersion(&mut version))?;
+
+            Ok(version.into())
+        }
+    }
+
     /**
     Gets the name of the process for the given process ID, cropped to the provided length.
     
@@ -489,7 +519,6 @@ impl NVML {
 
     * `InvalidArg`, if the device is invalid
     * `NotSupported`, if this `Device` or the OS does not support this feature
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
     
     # Platform Support
@@ -514,7 +543,7 @@ impl NVML {
                 &mut level
             ))?;
 
-            Ok(TopologyLevel::try_from(level)?)
+            Ok(TopologyLevel::from(level))
         }
     }
 
@@ -860,6 +889,11 @@ mod test {
         test(3, || nvml().sys_nvml_version())
     }
 
+    #[test]
+    fn sys_cuda_driver_version() {
+        test(3, || nvml().sys_cuda_driver_version())
+    }
+
     #[test]
     fn sys_process_name() {
         let nvml = nvml();
@@ -0,0 +1,119 @@
+/*
+Implements `Display` and `FromStr` for an enum from a table of strings.
+
+Parsing ignores surrounding whitespace and ASCII case. Enums that carry the raw
+value of variants the wrapper does not recognize name that variant after the
+table with `unknown Variant`; it is displayed as `Unknown (<value>)`, and
+parsed back from the same.
+*/
+macro_rules! display_from_str {
+    ($name:ident { $($variant:ident => $string:expr),+ $(,)* }) => {
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(match *self {
+                    $($name::$variant => $string,)+
+                })
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::error::Error;
+
+            fn from_str(s: &str) -> ::error::Result<Self> {
+                let s = s.trim();
+                let lower = s.to_lowercase();
+                $(
+                    if lower == $string.to_lowercase() {
+                        return Ok($name::$variant);
+                    }
+                )+
+
+                Err(::error::ErrorKind::UnexpectedString(stringify!($name), s.into()).into())
+            }
+        }
+    };
+    ($name:ident { $($variant:ident => $string:expr),+ $(,)* } unknown $unknown:ident) => {
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match *self {
+                    $($name::$variant => f.write_str($string),)+
+                    $name::$unknown(value) => write!(f, "Unknown ({})", value),
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::error::Error;
+
+            fn from_str(s: &str) -> ::error::Result<Self> {
+                let s = s.trim();
+                let lower = s.to_lowercase();
+                $(
+                    if lower == $string.to_lowercase() {
+                        return Ok($name::$variant);
+                    }
+                )+
+
+                let value = if lower.starts_with("unknown (") && lower.ends_with(')') {
+                    lower[9..lower.len() - 1].trim().parse().ok()
+                } else {
+                    None
+                };
+
+                match value {
+                    Some(value) => Ok($name::$unknown(value)),
+                    None => Err(::error::ErrorKind::UnexpectedString(stringify!($name), s.into()).into()),
+                }
+            }
+        }
+    };
+}
+
+/*
+Defines an enum that wraps a C enum, along with `as_c()` and `From` for the C
+type.
+
+Variants are given as `Variant = C_CONSTANT`. An `Unknown(u32)` variant is
+added that carries any value not in the table, so values returned by newer
+drivers are preserved rather than turned into errors.
+*/
+macro_rules! wrap_c_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident: $c_enum:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $c_variant:path
+            ),+ $(,)*
+        }
+    ) => {
+        $(#[$meta])*
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+            /// A value this version of the wrapper does not recognize.
+            Unknown(u32)
+        }
+
+        impl $name {
+            /// Returns the C enum variant equivalent for the given Rust enum variant.
+            pub fn as_c(&self) -> $c_enum {
+                match *self {
+                    $($name::$variant => $c_variant,)+
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<$c_enum> for $name {
+            fn from(enum_: $c_enum) -> Self {
+                match enum_ {
+                    $($c_variant => $name::$variant,)+
+                    value => $name::Unknown(value),
+                }
+            }
+        }
+    };
+}
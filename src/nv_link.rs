@@ -1,6 +1,6 @@
 use Device;
 use enum_wrappers::{bool_from_state, state_from_bool};
-use enum_wrappers::nv_link::{ErrorCounter, Capability};
+use enum_wrappers::nv_link::{ErrorCounter, Capability, RemoteDeviceType};
 use enums::nv_link::Counter;
 use error::{nvml_try, Result};
 use ffi::bindings::*;
@@ -172,6 +172,37 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         }
     }
 
+    /**
+    Gets the type of device on the other end of this `NvLink`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `link` or `Device` within this `NvLink` struct instance
+      is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    // Test written
+    #[inline]
+    pub fn remote_device_type(&self) -> Result<RemoteDeviceType> {
+        unsafe {
+            let mut device_type: nvmlIntNvLinkDeviceType_t = mem::zeroed();
+
+            nvml_try(nvmlDeviceGetNvLinkRemoteDeviceType(
+                self.device.unsafe_raw(),
+                self.link,
+                &mut device_type
+            ))?;
+
+            Ok(device_type.into())
+        }
+    }
+
     /**
     Gets the specified `ErrorCounter` value.
 
@@ -299,7 +330,7 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
                 &mut controls
             ))?;
 
-            Ok(UtilizationControl::try_from(controls)?)
+            Ok(UtilizationControl::from(controls))
         }
     }
 
@@ -481,6 +512,12 @@ mod test {
         })
     }
 
+    #[test]
+    fn remote_device_type() {
+        let nvml = nvml();
+        test_with_link(3, &nvml, |link| link.remote_device_type())
+    }
+
     #[test]
     fn error_counter() {
         let nvml = nvml();
@@ -510,11 +547,11 @@ mod test {
 
         let settings = UtilizationControl {
             units: UtilizationCountUnit::Cycles,
-            packet_filter: PacketTypes::NO_OP |
-                           PacketTypes::READ |
-                           PacketTypes::WRITE |
-                           PacketTypes::RATOM |
-                           PacketTypes::WITH_DATA
+            packet_filter: (PacketTypes::NO_OP |
+                            PacketTypes::READ |
+                            PacketTypes::WRITE |
+                            PacketTypes::RATOM |
+                            PacketTypes::WITH_DATA).into()
         };
 
         link.set_utilization_control(Counter::One, settings, false).unwrap()
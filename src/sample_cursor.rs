@@ -0,0 +1,300 @@
+use Device;
+use enum_wrappers::device::{SampleValueType, Sampling};
+use error::{nvml_try, ErrorKind, Result};
+use ffi::bindings::*;
+use std::cmp;
+use std::fmt;
+use std::mem;
+use std::os::raw::c_uint;
+use std::slice;
+use struct_wrappers::device::Sample;
+
+/**
+Streams the samples NVML buffers for a `Device` and `Sampling` type.
+
+Obtain this via `Device.sample_cursor()`.
+
+Each call to `.poll()` returns only the samples taken since the previous call;
+the cursor remembers the last timestamp it has seen. The buffer the samples are
+read into is kept between calls, so in the steady state each poll makes a
+single call into NVML (`Device.samples()` makes two).
+
+Rust's lifetimes will ensure both that the contained `Device` is valid for the
+lifetime of the `SampleCursor` struct and that the `NVML` instance will be valid
+for the duration of both.
+*/
+pub struct SampleCursor<'device, 'nvml: 'device> {
+    pub(crate) device: &'device Device<'nvml>,
+    pub(crate) sample_type: Sampling,
+    pub(crate) last_seen: u64,
+    pub(crate) buffer: Vec<nvmlSample_t>
+}
+
+impl<'device, 'nvml: 'device> SampleCursor<'device, 'nvml> {
+    /// Obtain the `Device` reference stored within this struct.
+    #[inline]
+    pub fn device(&self) -> &Device<'nvml> {
+        self.device
+    }
+
+    /// The type of samples this cursor reads.
+    #[inline]
+    pub fn sample_type(&self) -> &Sampling {
+        &self.sample_type
+    }
+
+    /// The CPU timestamp (in μs) of the newest sample returned so far, if any.
+    #[inline]
+    pub fn last_seen(&self) -> Option<u64> {
+        match self.last_seen {
+            0 => None,
+            timestamp => Some(timestamp)
+        }
+    }
+
+    /// Forget the last timestamp seen. The next `.poll()` will return every
+    /// sample in NVML's buffer.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.last_seen = 0;
+    }
+
+    /**
+    Gets the samples taken since the previous call, oldest first.
+
+    Samples are converted as they are iterated over; use
+    `Sample.system_time()` to turn their timestamps into `SystemTime`s.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if this query is not supported by the `Device`
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    No new samples is not an error; the returned iterator is simply empty.
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Checked against local
+    #[inline]
+    pub fn poll<'cursor>(&'cursor mut self) -> Result<NewSamples<'cursor>> {
+        // The buffer is sized by the first poll and grown whenever it fills
+        // up, in case NVML had more samples than it could hold
+        let (value_type, count) = loop {
+            if self.buffer.is_empty() {
+                match self.count()? {
+                    0 => return Ok(NewSamples::empty()),
+                    count => self.buffer = vec![unsafe { mem::zeroed() }; count as usize],
+                }
+            }
+
+            let capacity = self.buffer.len();
+            let filled = match self.fill() {
+                Ok(filled) if filled.1 < capacity => break filled,
+                Ok(filled) => Some(filled),
+                Err(ref e) if is_not_found(e) => return Ok(NewSamples::empty()),
+                Err(ref e) if is_insufficient_size(e) => None,
+                Err(e) => return Err(e),
+            };
+
+            match (filled, self.count()? as usize) {
+                (Some(filled), needed) if needed <= capacity => break filled,
+                (_, needed) => self.buffer = vec![unsafe { mem::zeroed() }; cmp::max(needed, capacity + 1)],
+            }
+        };
+
+        let value_type = SampleValueType::from(value_type);
+        // Some drivers return the sample at `last_seen` again
+        let count = retain_newer(&mut self.buffer[..count], self.last_seen);
+        let samples = &self.buffer[..count];
+        if let Some(newest) = samples.iter().map(|s| s.timeStamp).max() {
+            self.last_seen = newest;
+        }
+
+        Ok(NewSamples {
+            samples: samples.iter(),
+            value_type: Some(value_type)
+        })
+    }
+
+    // Reads into the buffer. Returns the value type and the number of samples
+    // written.
+    fn fill(&mut self) -> Result<(nvmlValueType_t, usize)> {
+        unsafe {
+            let mut val_type: nvmlValueType_t = mem::zeroed();
+            let mut count = self.buffer.len() as c_uint;
+
+            nvml_try(nvmlDeviceGetSamples(
+                self.device.unsafe_raw(),
+                self.sample_type.as_c(),
+                self.last_seen,
+                &mut val_type,
+                &mut count,
+                self.buffer.as_mut_ptr()
+            ))?;
+
+            Ok((val_type, count as usize))
+        }
+    }
+
+    // The number of samples NVML has since the last timestamp seen.
+    fn count(&self) -> Result<c_uint> {
+        unsafe {
+            let mut val_type: nvmlValueType_t = mem::zeroed();
+            let mut count: c_uint = mem::zeroed();
+
+            match nvml_try(nvmlDeviceGetSamples(
+                self.device.unsafe_raw(),
+                self.sample_type.as_c(),
+                self.last_seen,
+                &mut val_type,
+                &mut count,
+                // Indicates that we want the count
+                ::std::ptr::null_mut()
+            )) {
+                Err(ref e) if is_not_found(e) => Ok(0),
+                Err(e) => Err(e),
+                Ok(()) => Ok(count),
+            }
+        }
+    }
+}
+
+impl<'device, 'nvml: 'device> fmt::Debug for SampleCursor<'device, 'nvml> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SampleCursor")
+            .field("device", &self.device)
+            .field("sample_type", &self.sample_type)
+            .field("last_seen", &self.last_seen)
+            .field("buffer_len", &self.buffer.len())
+            .finish()
+    }
+}
+
+/// Iterator over the samples returned from `SampleCursor.poll()`.
+pub struct NewSamples<'cursor> {
+    samples: slice::Iter<'cursor, nvmlSample_t>,
+    value_type: Option<SampleValueType>
+}
+
+impl<'cursor> NewSamples<'cursor> {
+    fn empty() -> Self {
+        NewSamples {
+            samples: [].iter(),
+            value_type: None
+        }
+    }
+}
+
+impl<'cursor> Iterator for NewSamples<'cursor> {
+    type Item = Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Sample> {
+        let value_type = match self.value_type {
+            Some(ref value_type) => value_type,
+            None => return None,
+        };
+
+        self.samples
+            .next()
+            .map(|s| Sample::from_tag_and_struct(value_type, *s))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.samples.size_hint()
+    }
+}
+
+impl<'cursor> ExactSizeIterator for NewSamples<'cursor> {}
+
+impl<'cursor> fmt::Debug for NewSamples<'cursor> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NewSamples")
+            .field("remaining", &self.samples.len())
+            .field("value_type", &self.value_type)
+            .finish()
+    }
+}
+
+// Moves the samples newer than `last_seen` to the front, keeping their order.
+// Returns how many there are.
+fn retain_newer(samples: &mut [nvmlSample_t], last_seen: u64) -> usize {
+    let mut kept = 0;
+
+    for i in 0..samples.len() {
+        if samples[i].timeStamp > last_seen {
+            samples.swap(kept, i);
+            kept += 1;
+        }
+    }
+
+    kept
+}
+
+fn is_not_found(error: &::error::Error) -> bool {
+    match *error.kind() {
+        ErrorKind::NotFound => true,
+        _ => false,
+    }
+}
+
+fn is_insufficient_size(error: &::error::Error) -> bool {
+    match *error.kind() {
+        ErrorKind::InsufficientSize(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[deny(unused_mut)]
+mod test {
+    use super::retain_newer;
+    use enum_wrappers::device::Sampling;
+    use ffi::bindings::nvmlSample_t;
+    use std::mem;
+    use test_utils::*;
+
+    fn samples(timestamps: &[u64]) -> Vec<nvmlSample_t> {
+        timestamps
+            .iter()
+            .map(|&timestamp| {
+                let mut sample: nvmlSample_t = unsafe { mem::zeroed() };
+                sample.timeStamp = timestamp;
+                sample
+            })
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_buffer_is_filtered() {
+        // The driver returned the sample at the last timestamp seen again
+        let mut buffer = samples(&[100, 150, 200, 250]);
+        let kept = retain_newer(&mut buffer, 150);
+
+        let timestamps: Vec<u64> = buffer[..kept].iter().map(|s| s.timeStamp).collect();
+        assert_eq!(timestamps, vec![200, 250]);
+        assert_eq!(retain_newer(&mut buffer[..kept], 250), 0);
+    }
+
+    #[test]
+    fn poll() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let mut cursor = device.sample_cursor(Sampling::Power);
+
+        let newest = cursor.poll().expect("first poll").map(|s| s.timestamp).max();
+        if newest.is_some() {
+            assert_eq!(cursor.last_seen(), newest);
+        }
+
+        for sample in cursor.poll().expect("second poll") {
+            assert!(sample.timestamp > newest.unwrap_or(0));
+        }
+    }
+}
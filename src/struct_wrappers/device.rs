@@ -1,9 +1,15 @@
-use enum_wrappers::device::{BridgeChip, SampleValueType};
+use enum_wrappers::device::{BridgeChip, Clock, PerformanceState, SampleValueType, ThermalController,
+                            ThermalTarget};
 use enums::device::{UsedGpuMemory, SampleValue, FirmwareVersion};
 use error::{Result, ErrorKind};
 use ffi::bindings::*;
+use std::cmp;
 use std::ffi::{CStr, CString};
+use std::fmt;
 use std::os::raw::c_char;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MIB: u64 = 1024 * 1024;
 
 /// PCI information about a GPU device.
 // Checked against local
@@ -31,6 +37,12 @@ pub struct PciInfo {
     pub pci_sub_system_id: Option<u32>
 }
 
+impl fmt::Display for PciInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.bus_id)
+    }
+}
+
 impl PciInfo {
     /**
     Waiting for `TryFrom` to be stable. In the meantime, we do this.
@@ -129,6 +141,12 @@ pub struct BAR1MemoryInfo {
     pub used: u64
 }
 
+impl fmt::Display for BAR1MemoryInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} MiB / {} MiB", self.used / MIB, self.total / MIB)
+    }
+}
+
 impl From<nvmlBAR1Memory_t> for BAR1MemoryInfo {
     fn from(struct_: nvmlBAR1Memory_t) -> Self {
         BAR1MemoryInfo {
@@ -148,22 +166,18 @@ pub struct BridgeChipInfo {
     pub chip_type: BridgeChip
 }
 
-impl BridgeChipInfo {
-    /**
-    Construct `BridgeChipInfo` from the corresponding C struct.
-
-    # Errors
-
-    * `UnexpectedVariant`, for which you can read the docs for
-    */
-    pub fn try_from(struct_: nvmlBridgeChipInfo_t) -> Result<Self> {
-        let fw_version = FirmwareVersion::from(struct_.fwVersion);
-        let chip_type = BridgeChip::try_from(struct_.type_)?;
+impl fmt::Display for BridgeChipInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (Firmware: {})", self.chip_type, self.fw_version)
+    }
+}
 
-        Ok(BridgeChipInfo {
-            fw_version,
-            chip_type
-        })
+impl From<nvmlBridgeChipInfo_t> for BridgeChipInfo {
+    fn from(struct_: nvmlBridgeChipInfo_t) -> Self {
+        BridgeChipInfo {
+            fw_version: FirmwareVersion::from(struct_.fwVersion),
+            chip_type: BridgeChip::from(struct_.type_)
+        }
     }
 }
 
@@ -183,27 +197,30 @@ pub struct BridgeChipHierarchy {
     pub chip_count: u8
 }
 
-impl BridgeChipHierarchy {
-    /**
-    Construct `BridgeChipHierarchy` from the corresponding C struct.
+impl fmt::Display for BridgeChipHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chips = &self.chips_hierarchy[..cmp::min(self.chip_count as usize, self.chips_hierarchy.len())];
+        if chips.is_empty() {
+            return f.write_str("N/A");
+        }
 
-    # Errors
-    
-    * `UnexpectedVariant`, for which you can read the docs for
-    */
-    pub fn try_from(struct_: nvmlBridgeChipHierarchy_t) -> Result<Self> {
-        let chips_hierarchy: Result<Vec<BridgeChipInfo>> = struct_
-            .bridgeChipInfo
-            .iter()
-            .map(|bci| BridgeChipInfo::try_from(*bci))
-            .collect();
+        for (i, chip) in chips.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{}", chip)?;
+        }
 
-        let chips_hierarchy = chips_hierarchy?;
+        Ok(())
+    }
+}
 
-        Ok(BridgeChipHierarchy {
-            chips_hierarchy,
+impl From<nvmlBridgeChipHierarchy_t> for BridgeChipHierarchy {
+    fn from(struct_: nvmlBridgeChipHierarchy_t) -> Self {
+        BridgeChipHierarchy {
+            chips_hierarchy: struct_.bridgeChipInfo.iter().map(|bci| BridgeChipInfo::from(*bci)).collect(),
             chip_count: struct_.bridgeCount
-        })
+        }
     }
 }
 
@@ -218,6 +235,12 @@ pub struct ProcessInfo {
     pub used_gpu_memory: UsedGpuMemory
 }
 
+impl fmt::Display for ProcessInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PID {}: {}", self.pid, self.used_gpu_memory)
+    }
+}
+
 impl From<nvmlProcessInfo_t> for ProcessInfo {
     fn from(struct_: nvmlProcessInfo_t) -> Self {
         ProcessInfo {
@@ -238,6 +261,16 @@ pub struct EccErrorCounts {
     pub register_file: u64
 }
 
+impl fmt::Display for EccErrorCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Device Memory: {}, Register File: {}, L1 Cache: {}, L2 Cache: {}",
+            self.device_memory, self.register_file, self.l1_cache, self.l2_cache
+        )
+    }
+}
+
 impl From<nvmlEccErrorCounts_t> for EccErrorCounts {
     fn from(struct_: nvmlEccErrorCounts_t) -> Self {
         EccErrorCounts {
@@ -265,6 +298,12 @@ pub struct MemoryInfo {
     pub used: u64
 }
 
+impl fmt::Display for MemoryInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} MiB / {} MiB", self.used / MIB, self.total / MIB)
+    }
+}
+
 impl From<nvmlMemory_t> for MemoryInfo {
     fn from(struct_: nvmlMemory_t) -> Self {
         MemoryInfo {
@@ -289,6 +328,12 @@ pub struct Utilization {
     pub memory: u32
 }
 
+impl fmt::Display for Utilization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Gpu: {} %, Memory: {} %", self.gpu, self.memory)
+    }
+}
+
 impl From<nvmlUtilization_t> for Utilization {
     fn from(struct_: nvmlUtilization_t) -> Self {
         Utilization {
@@ -309,6 +354,12 @@ pub struct ViolationTime {
     pub violation_time: u64
 }
 
+impl fmt::Display for ViolationTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} us", self.violation_time / 1000)
+    }
+}
+
 impl From<nvmlViolationTime_t> for ViolationTime {
     fn from(struct_: nvmlViolationTime_t) -> Self {
         ViolationTime {
@@ -358,6 +409,30 @@ pub struct AccountingStats {
     pub time: u64
 }
 
+impl fmt::Display for AccountingStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.gpu_utilization {
+            Some(gpu) => write!(f, "Gpu: {} %, ", gpu)?,
+            None => f.write_str("Gpu: N/A, ")?,
+        }
+        match self.memory_utilization {
+            Some(memory) => write!(f, "Memory: {} %, ", memory)?,
+            None => f.write_str("Memory: N/A, ")?,
+        }
+        match self.max_memory_usage {
+            Some(usage) => write!(f, "Max Memory Usage: {} MiB, ", usage / MIB)?,
+            None => f.write_str("Max Memory Usage: N/A, ")?,
+        }
+
+        write!(
+            f,
+            "Time: {} ms, Is Running: {}",
+            self.time,
+            if self.is_running { "Yes" } else { "No" }
+        )
+    }
+}
+
 impl From<nvmlAccountingStats_t> for AccountingStats {
     fn from(struct_: nvmlAccountingStats_t) -> Self {
         let not_avail_u64 = (NVML_VALUE_NOT_AVAILABLE) as u64;
@@ -398,6 +473,12 @@ pub struct Sample {
     pub value: SampleValue
 }
 
+impl fmt::Display for Sample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} @ {}", self.value, self.timestamp)
+    }
+}
+
 impl Sample {
     /// Given a tag and an untagged union, returns a Rust enum with the correct
     /// union variant.
@@ -407,6 +488,165 @@ impl Sample {
             value: SampleValue::from_tag_and_union(tag, struct_.sampleValue)
         }
     }
+
+    /// The timestamp as a `SystemTime`.
+    ///
+    /// NVML timestamps are microseconds since the Unix epoch.
+    #[inline]
+    pub fn system_time(&self) -> SystemTime {
+        system_time(self.timestamp)
+    }
+}
+
+fn system_time(timestamp: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::new(timestamp / 1_000_000, (timestamp % 1_000_000) as u32 * 1000)
+}
+
+/**
+A `Sample` whose value has been converted into the unit of its `Sampling` type.
+
+Returned from the typed sample methods on `Device`, such as
+`Device.power_samples()`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypedSample<T> {
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    pub value: T
+}
+
+impl<T: fmt::Display> fmt::Display for TypedSample<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} @ {}", self.value, self.timestamp)
+    }
+}
+
+impl<T> TypedSample<T> {
+    /// The timestamp as a `SystemTime`.
+    ///
+    /// NVML timestamps are microseconds since the Unix epoch.
+    #[inline]
+    pub fn system_time(&self) -> SystemTime {
+        system_time(self.timestamp)
+    }
+}
+
+/// Histogram of the remapping availability of each memory bank on a device.
+///
+/// Each field is the number of banks that have the given amount of
+/// remapping availability left.
+// Checked against local
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RowRemapperHistogram {
+    /// Banks with the maximum remapping availability left.
+    pub max: u32,
+    /// Banks with high remapping availability left.
+    pub high: u32,
+    /// Banks with partial remapping availability left.
+    pub partial: u32,
+    /// Banks with low remapping availability left.
+    pub low: u32,
+    /// Banks with no remapping availability left.
+    pub none: u32
+}
+
+impl fmt::Display for RowRemapperHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Max: {}, High: {}, Partial: {}, Low: {}, None: {}",
+            self.max, self.high, self.partial, self.low, self.none
+        )
+    }
+}
+
+impl From<nvmlRowRemapperHistogramValues_t> for RowRemapperHistogram {
+    fn from(struct_: nvmlRowRemapperHistogramValues_t) -> Self {
+        RowRemapperHistogram {
+            max: struct_.max,
+            high: struct_.high,
+            partial: struct_.partial,
+            low: struct_.low,
+            none: struct_.none
+        }
+    }
+}
+
+/// The clock offset applied to a clock domain in a given performance state,
+/// along with the range of offsets that are allowed.
+///
+/// Values are in MHz.
+// Checked against local
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClockOffset {
+    pub clock_type: Clock,
+    pub power_state: PerformanceState,
+    /// The offset currently applied.
+    pub clock_offset_mhz: i32,
+    /// The smallest (most negative) offset that can be applied.
+    pub min_clock_offset_mhz: i32,
+    /// The largest offset that can be applied.
+    pub max_clock_offset_mhz: i32
+}
+
+impl fmt::Display for ClockOffset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}): {} MHz ({} MHz to {} MHz)",
+            self.clock_type,
+            self.power_state,
+            self.clock_offset_mhz,
+            self.min_clock_offset_mhz,
+            self.max_clock_offset_mhz
+        )
+    }
+}
+
+impl From<nvmlClockOffset_t> for ClockOffset {
+    fn from(struct_: nvmlClockOffset_t) -> Self {
+        ClockOffset {
+            clock_type: Clock::from(struct_.type_),
+            power_state: PerformanceState::from(struct_.pstate),
+            clock_offset_mhz: struct_.clockOffsetMHz,
+            min_clock_offset_mhz: struct_.minClockOffsetMHz,
+            max_clock_offset_mhz: struct_.maxClockOffsetMHz
+        }
+    }
+}
+
+/// Information about one of a device's thermal sensors. Temperatures are in °C.
+// Checked against local
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThermalSensor {
+    pub controller: ThermalController,
+    /// The part of the board this sensor measures.
+    pub target: ThermalTarget,
+    pub default_min_temp: i32,
+    pub default_max_temp: i32,
+    pub current_temp: i32
+}
+
+impl fmt::Display for ThermalSensor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}): {} C", self.target, self.controller, self.current_temp)
+    }
+}
+
+impl From<nvmlGpuThermalSettings_t__bindgen_ty_1> for ThermalSensor {
+    fn from(struct_: nvmlGpuThermalSettings_t__bindgen_ty_1) -> Self {
+        ThermalSensor {
+            controller: struct_.controller.into(),
+            target: struct_.target.into(),
+            default_min_temp: struct_.defaultMinTemp,
+            default_max_temp: struct_.defaultMaxTemp,
+            current_temp: struct_.currentTemp
+        }
+    }
 }
 
 #[cfg(test)]
@@ -444,4 +684,24 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn sample_system_time() {
+        use enums::device::SampleValue;
+        use std::time::{Duration, UNIX_EPOCH};
+        use super::{Sample, TypedSample};
+
+        let expected = UNIX_EPOCH + Duration::new(1_600_000_000, 250_000);
+        let sample = Sample {
+            timestamp: 1_600_000_000_000_250,
+            value: SampleValue::U32(0)
+        };
+        let typed = TypedSample {
+            timestamp: sample.timestamp,
+            value: 0
+        };
+
+        assert_eq!(sample.system_time(), expected);
+        assert_eq!(typed.system_time(), expected);
+    }
 }
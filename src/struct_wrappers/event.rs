@@ -1,7 +1,9 @@
+use bitmasks::Lossless;
 use bitmasks::event::EventTypes;
 use device::Device;
 use enums::event::XidError;
 use ffi::bindings::*;
+use std::fmt;
 
 /// Information about an event that has occurred.
 // Checked against local
@@ -14,8 +16,13 @@ pub struct EventData<'nvml> {
     and find out if they represent the same physical device.
     */
     pub device: Device<'nvml>,
-    /// Information about what specific event occurred.
-    pub event_type: EventTypes,
+    /**
+    Information about what specific event occurred.
+
+    Any bits that do not correspond to a flag in this version of the wrapper,
+    such as event types added by newer drivers, are kept in `unknown_bits`.
+    */
+    pub event_type: Lossless<EventTypes>,
     /**
     Stores the last XID error for the device for the
     nvmlEventTypeXidCriticalError event.
@@ -25,21 +32,29 @@ pub struct EventData<'nvml> {
     pub event_data: Option<XidError>
 }
 
+impl<'nvml> fmt::Display for EventData<'nvml> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.event_data {
+            Some(ref xid) => write!(f, "{} ({})", self.event_type, xid),
+            None => write!(f, "{}", self.event_type),
+        }
+    }
+}
+
 impl<'nvml> From<nvmlEventData_t> for EventData<'nvml> {
     /**
     Performs the conversion.
     
-    The `event_type` bitmask is created via the `EventTypes::from_bits_truncate`
-    method, meaning that any bits that don't correspond to flags present in this
-    version of the wrapper will be dropped.
+    Any bits of the event type that don't correspond to flags present in this
+    version of the wrapper are kept in `event_type.unknown_bits`.
     */
     fn from(struct_: nvmlEventData_t) -> Self {
-        let event_type = EventTypes::from_bits_truncate(struct_.eventType);
+        let event_type = Lossless::<EventTypes>::from_bits(struct_.eventType);
 
         EventData {
             device: struct_.device.into(),
             event_type,
-            event_data: if event_type.contains(EventTypes::CRITICAL_XID_ERROR) {
+            event_data: if event_type.flags.contains(EventTypes::CRITICAL_XID_ERROR) {
                 Some(match struct_.eventData {
                     999 => XidError::Unknown,
                     v => XidError::Value(v),
@@ -1,42 +1,67 @@
+use bitmasks::Lossless;
 use bitmasks::nv_link::PacketTypes;
 use enum_wrappers::nv_link::UtilizationCountUnit;
-use error::Result;
 use ffi::bindings::*;
+use std::fmt;
 
 /// Defines NvLink counter controls.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UtilizationControl {
     pub units: UtilizationCountUnit,
-    pub packet_filter: PacketTypes
-}
-
-impl UtilizationControl {
     /**
-    Waiting for `TryFrom` to be stable. In the meantime, we do this.
+    Any bits that do not correspond to a flag in this version of the wrapper are
+    kept in `unknown_bits` and passed back to NVML by `.as_c()`, so that reading
+    and then setting a control does not change it.
+    */
+    pub packet_filter: Lossless<PacketTypes>
+}
 
-    The `packet_filter` bitmask is created via the `PacketTypes::from_bits_truncate`
-    method, meaning that any bits that don't correspond to flags present in this
-    version of the wrapper will be dropped.
+impl fmt::Display for UtilizationControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.units, self.packet_filter)
+    }
+}
 
-    # Errors
+impl From<nvmlNvLinkUtilizationControl_t> for UtilizationControl {
+    /**
+    Performs the conversion.
 
-    * `UnexpectedVariant`, for which you can read the docs for
+    Any bits of the packet filter that don't correspond to flags present in this
+    version of the wrapper are kept in `packet_filter.unknown_bits`.
     */
-    pub fn try_from(struct_: nvmlNvLinkUtilizationControl_t) -> Result<Self> {
-        let bits = struct_.pktfilter as u32;
-
-        Ok(UtilizationControl {
-            units: UtilizationCountUnit::try_from(struct_.units)?,
-            packet_filter: PacketTypes::from_bits_truncate(bits)
-        })
+    fn from(struct_: nvmlNvLinkUtilizationControl_t) -> Self {
+        UtilizationControl {
+            units: UtilizationCountUnit::from(struct_.units),
+            packet_filter: Lossless::from_bits(u64::from(struct_.pktfilter))
+        }
     }
+}
 
+impl UtilizationControl {
     /// Obtain this struct's C counterpart.
     pub fn as_c(&self) -> nvmlNvLinkUtilizationControl_t {
         nvmlNvLinkUtilizationControl_t {
             units: self.units.as_c(),
-            pktfilter: self.packet_filter.bits()
+            pktfilter: self.packet_filter.bits() as nvmlNvLinkUtilizationCountPktTypes_t
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_packet_filter_bits_round_trip() {
+        let raw = nvmlNvLinkUtilizationControl_t {
+            units: UtilizationCountUnit::Packets.as_c(),
+            pktfilter: PacketTypes::READ.bits() | 1 << 20
+        };
+        let control = UtilizationControl::from(raw);
+
+        assert_eq!(control.packet_filter.flags, PacketTypes::READ);
+        assert_eq!(control.packet_filter.unknown_bits, 1 << 20);
+        assert_eq!(control.as_c().pktfilter, raw.pktfilter);
+    }
+}
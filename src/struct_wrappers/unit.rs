@@ -1,7 +1,9 @@
 use enum_wrappers::unit::FanState;
 use error::Result;
 use ffi::bindings::*;
+use std::cmp;
 use std::ffi::CStr;
+use std::fmt;
 
 /// Fan information readings for an entire S-class unit.
 // Checked against local
@@ -14,24 +16,30 @@ pub struct FansInfo {
     pub fans: Vec<FanInfo>
 }
 
-impl FansInfo {
-    /**
-    Construct `FansInfo` from the corresponding C struct.
-
-    # Errors
+impl fmt::Display for FansInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fans = &self.fans[..cmp::min(self.count as usize, self.fans.len())];
+        if fans.is_empty() {
+            return f.write_str("N/A");
+        }
 
-    * `UnexpectedVariant`, for which you can read the docs for
-    */
-    pub fn try_from(struct_: nvmlUnitFanSpeeds_t) -> Result<Self> {
-        let fans: Result<Vec<FanInfo>> =
-            struct_.fans.iter().map(|f| FanInfo::try_from(*f)).collect();
+        for (i, fan) in fans.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", fan)?;
+        }
 
-        let fans = fans?;
+        Ok(())
+    }
+}
 
-        Ok(FansInfo {
+impl From<nvmlUnitFanSpeeds_t> for FansInfo {
+    fn from(struct_: nvmlUnitFanSpeeds_t) -> Self {
+        FansInfo {
             count: struct_.count,
-            fans
-        })
+            fans: struct_.fans.iter().map(|f| FanInfo::from(*f)).collect()
+        }
     }
 }
 
@@ -46,19 +54,18 @@ pub struct FanInfo {
     pub state: FanState
 }
 
-impl FanInfo {
-    /**
-    Construct `FanInfo` from the corresponding C struct.
+impl fmt::Display for FanInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} RPM ({})", self.speed, self.state)
+    }
+}
 
-    # Errors
-    
-    * `UnexpectedVariant`, for which you can read the docs for
-    */
-    pub fn try_from(struct_: nvmlUnitFanInfo_t) -> Result<Self> {
-        Ok(FanInfo {
+impl From<nvmlUnitFanInfo_t> for FanInfo {
+    fn from(struct_: nvmlUnitFanInfo_t) -> Self {
+        FanInfo {
             speed: struct_.speed,
-            state: FanState::try_from(struct_.state)?
-        })
+            state: FanState::from(struct_.state)
+        }
     }
 }
 
@@ -93,6 +100,16 @@ pub struct PsuInfo {
     pub voltage: u32
 }
 
+impl fmt::Display for PsuInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} V, {} A, {} W",
+            self.state, self.voltage, self.current, self.power_draw
+        )
+    }
+}
+
 impl PsuInfo {
     /// Waiting for `TryFrom` to be stable. In the meantime, we do this.
     pub fn try_from(struct_: nvmlPSUInfo_t) -> Result<Self> {
@@ -121,6 +138,16 @@ pub struct UnitInfo {
     pub serial: String
 }
 
+impl fmt::Display for UnitInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (ID: {}, Serial: {}, Firmware: {})",
+            self.name, self.id, self.serial, self.firmware_version
+        )
+    }
+}
+
 impl UnitInfo {
     /// Waiting for `TryFrom` to be stable. In the meantime, we do this.
     pub fn try_from(struct_: nvmlUnitInfo_t) -> Result<Self> {
@@ -149,6 +176,12 @@ pub struct HwbcEntry {
     pub firmware_version: String
 }
 
+impl fmt::Display for HwbcEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (Firmware: {})", self.id, self.firmware_version)
+    }
+}
+
 impl HwbcEntry {
     /// Waiting for `TryFrom` to be stable. In the meantime, we do this.
     pub fn try_from(struct_: nvmlHwbcEntry_t) -> Result<Self> {
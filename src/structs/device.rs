@@ -1,6 +1,12 @@
 use enum_wrappers::device::OperationMode;
 #[cfg(target_os = "windows")]
 use enum_wrappers::device::DriverModel;
+use std::fmt;
+use std::mem;
+use std::os::raw::c_ulong;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer};
+use utils::{enabled_disabled, on_off, yes_no};
 
 /// Returned from `Device.auto_boosted_clocks_enabled()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -15,6 +21,31 @@ pub struct AutoBoostClocksEnabledInfo {
     pub is_enabled_default: bool
 }
 
+impl fmt::Display for AutoBoostClocksEnabledInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (Default: {})",
+            on_off(self.is_enabled),
+            on_off(self.is_enabled_default)
+        )
+    }
+}
+
+/// Returned from `Device.cuda_compute_capability()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CudaComputeCapability {
+    pub major: i32,
+    pub minor: i32
+}
+
+impl fmt::Display for CudaComputeCapability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
 /// Returned from `Device.decoder_utilization()` and
 /// `Device.encoder_utilization()`.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -25,6 +56,12 @@ pub struct UtilizationInfo {
     pub sampling_period: u32
 }
 
+impl fmt::Display for UtilizationInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} %", self.utilization)
+    }
+}
+
 /// Returned from `Device.driver_model()`
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -34,6 +71,13 @@ pub struct DriverModelState {
     pub pending: DriverModel
 }
 
+#[cfg(target_os = "windows")]
+impl fmt::Display for DriverModelState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_pending(f, &self.current, &self.pending)
+    }
+}
+
 /// Returned from `Device.is_ecc_enabled()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -42,6 +86,16 @@ pub struct EccModeState {
     pub pending_enabled: bool
 }
 
+impl fmt::Display for EccModeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_pending(
+            f,
+            enabled_disabled(self.currently_enabled),
+            enabled_disabled(self.pending_enabled)
+        )
+    }
+}
+
 /// Returned from `Device.gpu_operation_mode()`
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -50,6 +104,81 @@ pub struct OperationModeState {
     pub pending: OperationMode
 }
 
+impl fmt::Display for OperationModeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_pending(f, &self.current, &self.pending)
+    }
+}
+
+/**
+Returned from `Device.pcie_link_health()`
+
+PCIe links are routinely trained down to a lower generation (and sometimes a
+narrower width) while a GPU is idle to save power, so a link running below its
+maximum is only a problem while the GPU is busy. `.is_degraded()` takes this
+into account.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PcieLinkHealth {
+    pub current_link_gen: u32,
+    /// The max link generation possible with this device and system.
+    pub max_link_gen: u32,
+    /// The max link generation the device itself supports, regardless of the
+    /// system it is in.
+    ///
+    /// `None` if the device cannot report it.
+    pub gpu_max_link_gen: Option<u32>,
+    pub current_link_width: u32,
+    /// The max link width possible with this device and system.
+    pub max_link_width: u32,
+    /// GPU utilization (in percent) at the time the link was checked.
+    ///
+    /// `None` if the device cannot report it.
+    pub gpu_utilization: Option<u32>,
+    /// Whether or not the GPU was considered to be under load.
+    pub under_load: bool
+}
+
+impl fmt::Display for PcieLinkHealth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Gen {} x{} (Max: Gen {} x{})",
+            self.current_link_gen, self.current_link_width, self.max_link_gen, self.max_link_width
+        )
+    }
+}
+
+impl PcieLinkHealth {
+    /// Whether the link is running below the max generation possible with
+    /// this device and system.
+    pub fn is_gen_below_max(&self) -> bool {
+        self.current_link_gen < self.max_link_gen
+    }
+
+    /// Whether the link is running below the max width possible with this
+    /// device and system.
+    pub fn is_width_below_max(&self) -> bool {
+        self.current_link_width < self.max_link_width
+    }
+
+    /// Whether the system limits the link to a lower generation than the
+    /// device supports (e.g. a gen 4 GPU in a gen 3 slot).
+    pub fn is_limited_by_system(&self) -> bool {
+        match self.gpu_max_link_gen {
+            Some(gpu_max) => self.max_link_gen < gpu_max,
+            None => false,
+        }
+    }
+
+    /// Whether the link is running below its max generation or width while
+    /// the GPU is under load.
+    pub fn is_degraded(&self) -> bool {
+        self.under_load && (self.is_gen_below_max() || self.is_width_below_max())
+    }
+}
+
 /// Returned from `Device.power_management_limit_constraints()`
 ///
 /// Values are in milliwatts.
@@ -59,3 +188,430 @@ pub struct PowerManagementConstraints {
     pub min_limit: u32,
     pub max_limit: u32
 }
+
+impl fmt::Display for PowerManagementConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.2} W to {:.2} W",
+            f64::from(self.min_limit) / 1000.0,
+            f64::from(self.max_limit) / 1000.0
+        )
+    }
+}
+
+/// Returned from `Device.min_max_fan_speed()`
+///
+/// Values are percentages of the maximum fan speed.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FanSpeedConstraints {
+    pub min_speed: u32,
+    pub max_speed: u32
+}
+
+impl fmt::Display for FanSpeedConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} % to {} %", self.min_speed, self.max_speed)
+    }
+}
+
+/// Returned from `Device.remapped_rows()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RemappedRowsInfo {
+    /// Number of rows remapped due to correctable errors.
+    pub correctable_rows: u32,
+    /// Number of rows remapped due to uncorrectable errors.
+    pub uncorrectable_rows: u32,
+    /// Whether or not remappings are pending (they need a GPU reset to take
+    /// effect).
+    pub is_pending: bool,
+    /// Whether or not a remapping has failed in the past.
+    ///
+    /// If this is true the device should be considered unhealthy.
+    pub failure_occurred: bool
+}
+
+impl fmt::Display for RemappedRowsInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Correctable Error: {}, Uncorrectable Error: {}, Pending: {}, Remapping Failure Occurred: {}",
+            self.correctable_rows,
+            self.uncorrectable_rows,
+            yes_no(self.is_pending),
+            yes_no(self.failure_occurred)
+        )
+    }
+}
+
+/**
+A set of logical CPUs.
+
+NVML describes CPU affinity as an array of bitmasks (64 CPUs per `c_ulong` on
+64-bit machines, 32 on 32-bit machines); this is that array decoded into a
+sorted list of CPU indices.
+
+Returned from `Device.cpu_affinity_set()` and
+`Device.cpu_affinity_within_scope()`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CpuSet {
+    cpus: Vec<u32>
+}
+
+// Goes through `from_cpus()` so that `contains()` can rely on the CPUs being
+// sorted and unique.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CpuSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            cpus: Vec<u32>
+        }
+
+        Raw::deserialize(deserializer).map(|raw| CpuSet::from_cpus(raw.cpus))
+    }
+}
+
+impl fmt::Display for CpuSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_list_string())
+    }
+}
+
+impl CpuSet {
+    /// Decode an NVML-style array of bitmasks.
+    pub fn from_bitmask(mask: &[c_ulong]) -> Self {
+        CpuSet {
+            cpus: decode_bitmask(mask)
+        }
+    }
+
+    /// Create a set from a list of CPU indices. Duplicates are ignored.
+    pub fn from_cpus<I: IntoIterator<Item = u32>>(cpus: I) -> Self {
+        CpuSet {
+            cpus: sorted_unique(cpus)
+        }
+    }
+
+    /// Encode this set as an NVML-style array of bitmasks.
+    ///
+    /// The array is as short as possible; it is empty if the set is.
+    pub fn to_bitmask(&self) -> Vec<c_ulong> {
+        encode_bitmask(&self.cpus)
+    }
+
+    /// The CPU indices in this set, in ascending order.
+    pub fn cpus(&self) -> &[u32] {
+        &self.cpus
+    }
+
+    pub fn contains(&self, cpu: u32) -> bool {
+        self.cpus.binary_search(&cpu).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cpus.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cpus.is_empty()
+    }
+
+    /**
+    Render this set in the kernel's list format (e.g. `0-3,8,10-11`).
+
+    This is the format accepted by `taskset --cpu-list` and
+    `numactl --physcpubind`.
+    */
+    pub fn to_list_string(&self) -> String {
+        format_list(&self.cpus)
+    }
+
+    /**
+    Arguments that pin a command to this set when passed to `taskset`.
+
+    For example, `["-c", "0-3,8"]`; run `taskset -c 0-3,8 <command>`.
+    */
+    pub fn taskset_args(&self) -> Vec<String> {
+        vec!["-c".into(), self.to_list_string()]
+    }
+
+    /**
+    Arguments that pin a command to this set when passed to `numactl`.
+
+    For example, `["--physcpubind=0-3,8"]`; run
+    `numactl --physcpubind=0-3,8 <command>`.
+    */
+    pub fn numactl_args(&self) -> Vec<String> {
+        vec![format!("--physcpubind={}", self.to_list_string())]
+    }
+}
+
+/**
+A set of NUMA nodes.
+
+NVML describes memory affinity as an array of bitmasks in the same layout it
+uses for CPU affinity; this is that array decoded into a sorted list of node
+indices.
+
+Returned from `Device.memory_affinity()`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct NodeSet {
+    nodes: Vec<u32>
+}
+
+// Goes through `from_nodes()`, see `CpuSet`.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for NodeSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            nodes: Vec<u32>
+        }
+
+        Raw::deserialize(deserializer).map(|raw| NodeSet::from_nodes(raw.nodes))
+    }
+}
+
+impl fmt::Display for NodeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_list_string())
+    }
+}
+
+impl NodeSet {
+    /// Decode an NVML-style array of bitmasks.
+    pub fn from_bitmask(mask: &[c_ulong]) -> Self {
+        NodeSet {
+            nodes: decode_bitmask(mask)
+        }
+    }
+
+    /// Create a set from a list of node indices. Duplicates are ignored.
+    pub fn from_nodes<I: IntoIterator<Item = u32>>(nodes: I) -> Self {
+        NodeSet {
+            nodes: sorted_unique(nodes)
+        }
+    }
+
+    /// Encode this set as an NVML-style array of bitmasks.
+    ///
+    /// The array is as short as possible; it is empty if the set is.
+    pub fn to_bitmask(&self) -> Vec<c_ulong> {
+        encode_bitmask(&self.nodes)
+    }
+
+    /// The node indices in this set, in ascending order.
+    pub fn nodes(&self) -> &[u32] {
+        &self.nodes
+    }
+
+    pub fn contains(&self, node: u32) -> bool {
+        self.nodes.binary_search(&node).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Render this set in the kernel's list format (e.g. `0-1,3`).
+    pub fn to_list_string(&self) -> String {
+        format_list(&self.nodes)
+    }
+
+    /**
+    Arguments that bind both the CPUs and the memory allocations of a command
+    to these nodes when passed to `numactl`.
+
+    For example, `["--cpunodebind=0", "--membind=0"]`; run
+    `numactl --cpunodebind=0 --membind=0 <command>`.
+    */
+    pub fn numactl_args(&self) -> Vec<String> {
+        let list = self.to_list_string();
+        vec![format!("--cpunodebind={}", list), format!("--membind={}", list)]
+    }
+}
+
+// Writes `current`, followed by `pending` if a change is waiting on a reboot
+// or reset, as nvidia-smi does.
+fn write_pending<T: fmt::Display + PartialEq + ?Sized>(
+    f: &mut fmt::Formatter,
+    current: &T,
+    pending: &T
+) -> fmt::Result {
+    if current == pending {
+        write!(f, "{}", current)
+    } else {
+        write!(f, "{} (Pending: {})", current, pending)
+    }
+}
+
+fn bits_per_word() -> u32 {
+    (mem::size_of::<c_ulong>() * 8) as u32
+}
+
+fn decode_bitmask(mask: &[c_ulong]) -> Vec<u32> {
+    let bits = bits_per_word();
+    let mut indices = Vec::new();
+
+    for (word_idx, word) in mask.iter().enumerate() {
+        for bit in 0..bits {
+            if word & (1 << bit) != 0 {
+                indices.push(word_idx as u32 * bits + bit);
+            }
+        }
+    }
+
+    indices
+}
+
+// Expects sorted indices.
+fn encode_bitmask(indices: &[u32]) -> Vec<c_ulong> {
+    let bits = bits_per_word();
+    let len = indices.last().map_or(0, |last| (last / bits) as usize + 1);
+    let mut mask: Vec<c_ulong> = vec![0; len];
+
+    for index in indices {
+        mask[(index / bits) as usize] |= 1 << (index % bits);
+    }
+
+    mask
+}
+
+fn sorted_unique<I: IntoIterator<Item = u32>>(indices: I) -> Vec<u32> {
+    let mut indices: Vec<u32> = indices.into_iter().collect();
+    indices.sort();
+    indices.dedup();
+    indices
+}
+
+// Expects sorted, deduplicated indices.
+fn format_list(indices: &[u32]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut iter = indices.iter().cloned().peekable();
+
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end += 1;
+            iter.next();
+        }
+
+        if start == end {
+            ranges.push(start.to_string());
+        } else {
+            ranges.push(format!("{}-{}", start, end));
+        }
+    }
+
+    ranges.join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pending_state_display() {
+        let state = EccModeState {
+            currently_enabled: true,
+            pending_enabled: true
+        };
+        assert_eq!(state.to_string(), "Enabled");
+
+        let state = EccModeState {
+            currently_enabled: true,
+            pending_enabled: false
+        };
+        assert_eq!(state.to_string(), "Enabled (Pending: Disabled)");
+    }
+
+    #[test]
+    fn pcie_link_degraded_only_under_load() {
+        let mut health = PcieLinkHealth {
+            current_link_gen: 1,
+            max_link_gen: 4,
+            gpu_max_link_gen: Some(4),
+            current_link_width: 16,
+            max_link_width: 16,
+            gpu_utilization: Some(0),
+            under_load: false
+        };
+        assert!(health.is_gen_below_max());
+        assert!(!health.is_degraded());
+
+        health.under_load = true;
+        assert!(health.is_degraded());
+
+        health.current_link_gen = 4;
+        assert!(!health.is_degraded());
+
+        health.current_link_width = 8;
+        assert!(health.is_degraded());
+    }
+
+    #[test]
+    fn cpu_set_round_trip() {
+        let bits = bits_per_word();
+        let mask: Vec<c_ulong> = vec![0b1011, 0b11];
+        let set = CpuSet::from_bitmask(&mask);
+
+        assert_eq!(set.cpus(), &[0, 1, 3, bits, bits + 1][..]);
+        assert!(set.contains(bits));
+        assert!(!set.contains(2));
+        assert_eq!(set.to_bitmask(), mask);
+    }
+
+    #[test]
+    fn empty_set() {
+        let set = CpuSet::from_bitmask(&[0, 0]);
+
+        assert!(set.is_empty());
+        assert!(set.to_bitmask().is_empty());
+        assert_eq!(set.to_list_string(), "");
+    }
+
+    #[test]
+    fn list_string() {
+        let set = CpuSet::from_cpus(vec![8, 0, 1, 2, 3, 10, 11, 3]);
+
+        assert_eq!(set.to_list_string(), "0-3,8,10-11");
+        assert_eq!(set.taskset_args(), vec!["-c", "0-3,8,10-11"]);
+        assert_eq!(set.numactl_args(), vec!["--physcpubind=0-3,8,10-11"]);
+    }
+
+    #[test]
+    fn node_set_args() {
+        let set = NodeSet::from_nodes(vec![1, 0]);
+
+        assert_eq!(set.to_list_string(), "0-1");
+        assert_eq!(set.numactl_args(), vec!["--cpunodebind=0-1", "--membind=0-1"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_sets_are_sorted() {
+        use serde::de::value::{Error, MapDeserializer};
+
+        let fields = vec![("cpus", vec![5u32, 1, 1])];
+        let set = CpuSet::deserialize(MapDeserializer::<_, Error>::new(fields.into_iter()))
+            .expect("deserialized");
+        assert_eq!(set.cpus(), &[1, 5][..]);
+        assert!(set.contains(5));
+
+        let fields = vec![("nodes", vec![3u32, 0])];
+        let set = NodeSet::deserialize(MapDeserializer::<_, Error>::new(fields.into_iter()))
+            .expect("deserialized");
+        assert_eq!(set.nodes(), &[0, 3][..]);
+    }
+}
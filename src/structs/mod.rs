@@ -1,2 +1,3 @@
 pub mod device;
 pub mod nv_link;
+pub mod nvml;
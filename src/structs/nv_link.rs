@@ -1,3 +1,5 @@
+use std::fmt;
+
 /// Returned by `NvLink.utilization_counter()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -7,3 +9,9 @@ pub struct UtilizationCounter {
     /// Send counter value
     pub send: u64
 }
+
+impl fmt::Display for UtilizationCounter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rx: {}, Tx: {}", self.receive, self.send)
+    }
+}
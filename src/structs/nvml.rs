@@ -0,0 +1,52 @@
+use std::fmt;
+
+/**
+Returned from `NVML.sys_cuda_driver_version()`
+
+NVML reports the version as a single integer (e.g. `11040` for CUDA 11.4);
+this is that integer split into its parts.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CudaDriverVersion {
+    pub major: i32,
+    pub minor: i32
+}
+
+impl fmt::Display for CudaDriverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl From<i32> for CudaDriverVersion {
+    fn from(version: i32) -> Self {
+        CudaDriverVersion {
+            major: version / 1000,
+            minor: (version % 1000) / 10
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_raw_version() {
+        assert_eq!(
+            CudaDriverVersion::from(11040),
+            CudaDriverVersion {
+                major: 11,
+                minor: 4
+            }
+        );
+        assert_eq!(
+            CudaDriverVersion::from(9020),
+            CudaDriverVersion {
+                major: 9,
+                minor: 2
+            }
+        );
+    }
+}
@@ -3,12 +3,19 @@ use NVML;
 #[cfg(not(feature = "test-local"))]
 use NvLink;
 use Unit;
+use bitmasks::Lossless;
 use bitmasks::device::*;
 use bitmasks::event::*;
 use enum_wrappers::device::*;
+#[cfg(not(feature = "test-local"))]
+use enum_wrappers::nv_link::RemoteDeviceType;
 use enums::unit::*;
 use error::*;
 use event::EventSet;
+use high_level::device_config::ConfigPlan;
+use high_level::health::HealthReport;
+#[cfg(target_os = "linux")]
+use high_level::topology::{NvLinkGraph, Topology};
 use std::fmt::Debug;
 use struct_wrappers::device::*;
 use struct_wrappers::event::*;
@@ -16,6 +23,7 @@ use struct_wrappers::event::*;
 use struct_wrappers::nv_link::*;
 use struct_wrappers::unit::*;
 use structs::device::*;
+use structs::nvml::*;
 #[cfg(not(feature = "test-local"))]
 use structs::nv_link::*;
 
@@ -78,23 +86,48 @@ impl ShouldPrint for MemoryInfo {}
 impl ShouldPrint for PciInfo {}
 impl ShouldPrint for PerformanceState {}
 impl ShouldPrint for PowerManagementConstraints {}
+impl ShouldPrint for RemappedRowsInfo {}
+impl ShouldPrint for FanSpeedConstraints {}
+impl ShouldPrint for FanControlPolicy {}
+impl ShouldPrint for DeviceArchitecture {}
+impl ShouldPrint for CudaComputeCapability {}
+impl ShouldPrint for CudaDriverVersion {}
+impl ShouldPrint for ClockOffset {}
+impl ShouldPrint for Vec<ThermalSensor> {}
+impl ShouldPrint for PcieLinkMaxSpeed {}
+impl ShouldPrint for BusType {}
+impl ShouldPrint for PcieLinkHealth {}
+impl ShouldPrint for CpuSet {}
+impl ShouldPrint for NodeSet {}
+impl ShouldPrint for P2pStatus {}
+impl ShouldPrint for RowRemapperHistogram {}
 impl ShouldPrint for ThrottleReasons {}
 impl ShouldPrint for ViolationTime {}
 impl ShouldPrint for AccountingStats {}
 impl ShouldPrint for EventTypes {}
+impl<T: Debug> ShouldPrint for Lossless<T> {}
 impl<'nvml> ShouldPrint for EventData<'nvml> {}
 impl ShouldPrint for FansInfo {}
 impl ShouldPrint for LedState {}
 impl ShouldPrint for PsuInfo {}
+impl ShouldPrint for HealthReport {}
+impl ShouldPrint for ConfigPlan {}
 impl ShouldPrint for UnitInfo {}
 #[cfg(not(feature = "test-local"))]
 impl ShouldPrint for UtilizationControl {}
 #[cfg(not(feature = "test-local"))]
+impl ShouldPrint for RemoteDeviceType {}
+#[cfg(not(feature = "test-local"))]
 impl ShouldPrint for UtilizationCounter {}
 
 #[cfg(target_os = "windows")]
 impl ShouldPrint for DriverModelState {}
 
+#[cfg(target_os = "linux")]
+impl ShouldPrint for Topology {}
+#[cfg(target_os = "linux")]
+impl ShouldPrint for NvLinkGraph {}
+
 pub fn nvml() -> NVML {
     NVML::init().expect("initialized library")
 }
@@ -8,6 +8,7 @@ use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_uint;
 use struct_wrappers::unit::{FansInfo, PsuInfo, UnitInfo};
+use units::TypedUnit;
 
 /**
 Struct that represents a unit. 
@@ -137,7 +138,6 @@ impl<'nvml> Unit<'nvml> {
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if the unit is invalid
     * `NotSupported`, if this is not an S-class product
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
     
     # Device Support
@@ -152,7 +152,7 @@ impl<'nvml> Unit<'nvml> {
             let mut fans_info: nvmlUnitFanSpeeds_t = mem::zeroed();
             nvml_try(nvmlUnitGetFanSpeedInfo(self.unit, &mut fans_info))?;
 
-            Ok(FansInfo::try_from(fans_info)?)
+            Ok(FansInfo::from(fans_info))
         }
     }
 
@@ -298,6 +298,16 @@ impl<'nvml> Unit<'nvml> {
         unsafe { nvml_try(nvmlUnitSetLedState(self.unit, color.as_c())) }
     }
 
+    /**
+    Obtain a view of this `Unit` whose methods return strongly typed units.
+
+    See the `units` module for details.
+    */
+    #[inline]
+    pub fn typed<'unit>(&'unit self) -> TypedUnit<'unit, 'nvml> {
+        TypedUnit { unit: self }
+    }
+
     /// Consume the struct and obtain the raw unit handle that it contains.
     #[inline]
     pub fn into_raw(self) -> nvmlUnit_t {
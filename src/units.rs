@@ -0,0 +1,529 @@
+/*!
+Strongly typed physical units.
+
+The raw methods on `Device` and `Unit` return plain integers whose unit is only
+documented (milliwatts for `Device.power_usage()`, watts for
+`PsuInfo.power_draw`, MHz for clocks...). The types in this module carry their
+unit, so mixing them up is a compile error rather than a bug.
+
+This layer is opt-in: obtain a `TypedDevice` via `Device.typed()` (or a
+`TypedUnit` via `Unit.typed()`) and call the same methods you would on the
+raw struct. The raw methods are unchanged.
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+# use nvml::NVML;
+# use nvml::error::Result;
+# fn main() {
+#     test().unwrap();
+# }
+# fn test() -> Result<()> {
+use nvml::enum_wrappers::device::Clock;
+
+let nvml = NVML::init()?;
+let device = nvml.device_by_index(0)?;
+let typed = device.typed();
+
+let power = typed.power_usage()?;
+let limits = typed.power_management_limit_constraints()?;
+println!("drawing {} of at most {}", power, limits.max);
+// Saturates at zero, as the power draw can briefly exceed the limit
+println!("{:.1} W of headroom", (limits.max - power).as_watts());
+
+let clock = typed.clock_info(Clock::Graphics)?;
+println!("graphics clock at {:.2} GHz", clock.as_gigahertz());
+# Ok(())
+# }
+```
+
+The wrapped value is always available as `.0` for use with the raw methods
+(such as `Device.set_power_management_limit()`).
+*/
+
+use Device;
+use Unit;
+use enum_wrappers::device::{Clock, ClockId, TemperatureSensor, TemperatureThreshold};
+use enums::unit::TemperatureReading;
+use error::Result;
+use std::fmt;
+use std::ops::{Add, Sub};
+use struct_wrappers::device::{BAR1MemoryInfo, MemoryInfo, Utilization};
+use structs::device::PowerManagementConstraints;
+
+// Conversions, arithmetic and `Display` shared by every unit. Arithmetic
+// saturates rather than overflowing.
+macro_rules! unit {
+    ($name:ident, $raw:ty, $symbol:expr) => {
+        impl From<$raw> for $name {
+            #[inline]
+            fn from(value: $raw) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $raw {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            #[inline]
+            fn add(self, other: $name) -> $name {
+                $name(self.0.saturating_add(other.0))
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            #[inline]
+            fn sub(self, other: $name) -> $name {
+                $name(self.0.saturating_sub(other.0))
+            }
+        }
+
+        impl $name {
+            /// Subtract `other`, returning `None` if the result would not fit.
+            #[inline]
+            pub fn checked_sub(self, other: $name) -> Option<$name> {
+                self.0.checked_sub(other.0).map($name)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{} {}", self.0, $symbol)
+            }
+        }
+    };
+}
+
+/// Power, in milliwatts.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Milliwatts(pub u32);
+
+unit!(Milliwatts, u32, "mW");
+
+impl Milliwatts {
+    /// Create a value from whole watts, saturating at `u32::MAX` milliwatts.
+    #[inline]
+    pub fn from_watts(watts: u32) -> Self {
+        Milliwatts(watts.saturating_mul(1000))
+    }
+
+    /// The value in watts.
+    #[inline]
+    pub fn as_watts(&self) -> f64 {
+        f64::from(self.0) / 1000.0
+    }
+}
+
+/// Frequency, in megahertz.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Megahertz(pub u32);
+
+unit!(Megahertz, u32, "MHz");
+
+impl Megahertz {
+    /// The value in hertz.
+    #[inline]
+    pub fn as_hertz(&self) -> u64 {
+        u64::from(self.0) * 1_000_000
+    }
+
+    /// The value in gigahertz.
+    #[inline]
+    pub fn as_gigahertz(&self) -> f64 {
+        f64::from(self.0) / 1000.0
+    }
+}
+
+/// Temperature, in degrees Celsius.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Celsius(pub i32);
+
+unit!(Celsius, i32, "°C");
+
+impl Celsius {
+    // NVML reports most temperatures unsigned; none come anywhere near
+    // overflowing an `i32`
+    fn from_unsigned(value: u32) -> Self {
+        Celsius(value as i32)
+    }
+
+    /// The value in degrees Fahrenheit.
+    #[inline]
+    pub fn as_fahrenheit(&self) -> f64 {
+        f64::from(self.0) * 9.0 / 5.0 + 32.0
+    }
+
+    /// The value in kelvin.
+    #[inline]
+    pub fn as_kelvin(&self) -> f64 {
+        f64::from(self.0) + 273.15
+    }
+}
+
+/// An amount of memory, in bytes.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bytes(pub u64);
+
+unit!(Bytes, u64, "B");
+
+impl Bytes {
+    /// Create a value from mebibytes, saturating at `u64::MAX` bytes.
+    #[inline]
+    pub fn from_mib(mib: u64) -> Self {
+        Bytes(mib.saturating_mul(1024 * 1024))
+    }
+
+    /// The value in kibibytes.
+    #[inline]
+    pub fn as_kib(&self) -> f64 {
+        self.0 as f64 / 1024.0
+    }
+
+    /// The value in mebibytes.
+    #[inline]
+    pub fn as_mib(&self) -> f64 {
+        self.0 as f64 / (1024.0 * 1024.0)
+    }
+
+    /// The value in gibibytes.
+    #[inline]
+    pub fn as_gib(&self) -> f64 {
+        self.0 as f64 / (1024.0 * 1024.0 * 1024.0)
+    }
+}
+
+/// A percentage, from 0 to 100.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Percent(pub u32);
+
+unit!(Percent, u32, "%");
+
+impl Percent {
+    /// The value as a fraction, from 0.0 to 1.0.
+    #[inline]
+    pub fn as_fraction(&self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+}
+
+/// Typed version of `PowerManagementConstraints`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerLimits {
+    pub min: Milliwatts,
+    pub max: Milliwatts
+}
+
+impl PowerLimits {
+    /// Whether `limit` falls within these constraints (inclusive).
+    #[inline]
+    pub fn contains(&self, limit: Milliwatts) -> bool {
+        self.min <= limit && limit <= self.max
+    }
+}
+
+impl From<PowerManagementConstraints> for PowerLimits {
+    fn from(constraints: PowerManagementConstraints) -> Self {
+        PowerLimits {
+            min: Milliwatts(constraints.min_limit),
+            max: Milliwatts(constraints.max_limit)
+        }
+    }
+}
+
+/// Typed version of `MemoryInfo` and `BAR1MemoryInfo`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryUsage {
+    pub free: Bytes,
+    pub total: Bytes,
+    pub used: Bytes
+}
+
+impl MemoryUsage {
+    /// The used memory as a fraction of the total, from 0.0 to 1.0. 0.0 if the
+    /// total is 0.
+    #[inline]
+    pub fn used_fraction(&self) -> f64 {
+        match self.total.0 {
+            0 => 0.0,
+            total => self.used.0 as f64 / total as f64,
+        }
+    }
+}
+
+impl From<MemoryInfo> for MemoryUsage {
+    fn from(info: MemoryInfo) -> Self {
+        MemoryUsage {
+            free: Bytes(info.free),
+            total: Bytes(info.total),
+            used: Bytes(info.used)
+        }
+    }
+}
+
+impl From<BAR1MemoryInfo> for MemoryUsage {
+    fn from(info: BAR1MemoryInfo) -> Self {
+        MemoryUsage {
+            free: Bytes(info.free),
+            total: Bytes(info.total),
+            used: Bytes(info.used)
+        }
+    }
+}
+
+/// Typed version of `Utilization`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UtilizationRates {
+    /// Percent of time over the past sample period during which one or more
+    /// kernels was executing on the GPU.
+    pub gpu: Percent,
+    /// Percent of time over the past sample period during which global (device)
+    /// memory was being read or written to.
+    pub memory: Percent
+}
+
+impl From<Utilization> for UtilizationRates {
+    fn from(utilization: Utilization) -> Self {
+        UtilizationRates {
+            gpu: Percent(utilization.gpu),
+            memory: Percent(utilization.memory)
+        }
+    }
+}
+
+/**
+Typed view of a `Device`.
+
+Obtain this via `Device.typed()`. Each method calls the `Device` method of the
+same name and wraps the result in its unit; see those methods for errors and
+device support.
+*/
+#[derive(Debug)]
+pub struct TypedDevice<'device, 'nvml: 'device> {
+    pub(crate) device: &'device Device<'nvml>
+}
+
+impl<'device, 'nvml: 'device> TypedDevice<'device, 'nvml> {
+    /// Obtain the `Device` reference stored within this struct.
+    #[inline]
+    pub fn device(&self) -> &Device<'nvml> {
+        self.device
+    }
+
+    /// `Device.power_usage()`.
+    #[inline]
+    pub fn power_usage(&self) -> Result<Milliwatts> {
+        self.device.power_usage().map(Milliwatts)
+    }
+
+    /// `Device.power_management_limit()`.
+    #[inline]
+    pub fn power_management_limit(&self) -> Result<Milliwatts> {
+        self.device.power_management_limit().map(Milliwatts)
+    }
+
+    /// `Device.power_management_limit_default()`.
+    #[inline]
+    pub fn power_management_limit_default(&self) -> Result<Milliwatts> {
+        self.device.power_management_limit_default().map(Milliwatts)
+    }
+
+    /// `Device.power_management_limit_constraints()`.
+    #[inline]
+    pub fn power_management_limit_constraints(&self) -> Result<PowerLimits> {
+        self.device.power_management_limit_constraints().map(PowerLimits::from)
+    }
+
+    /// `Device.enforced_power_limit()`.
+    #[inline]
+    pub fn enforced_power_limit(&self) -> Result<Milliwatts> {
+        self.device.enforced_power_limit().map(Milliwatts)
+    }
+
+    /// `Device.clock_info()`.
+    #[inline]
+    pub fn clock_info(&self, clock_type: Clock) -> Result<Megahertz> {
+        self.device.clock_info(clock_type).map(Megahertz)
+    }
+
+    /// `Device.clock()`.
+    #[inline]
+    pub fn clock(&self, clock_type: Clock, clock_id: ClockId) -> Result<Megahertz> {
+        self.device.clock(clock_type, clock_id).map(Megahertz)
+    }
+
+    /// `Device.max_clock_info()`.
+    #[inline]
+    pub fn max_clock_info(&self, clock_type: Clock) -> Result<Megahertz> {
+        self.device.max_clock_info(clock_type).map(Megahertz)
+    }
+
+    /// `Device.max_customer_boost_clock()`.
+    #[inline]
+    pub fn max_customer_boost_clock(&self, clock_type: Clock) -> Result<Megahertz> {
+        self.device.max_customer_boost_clock(clock_type).map(Megahertz)
+    }
+
+    /// `Device.applications_clock()`.
+    #[inline]
+    pub fn applications_clock(&self, clock_type: Clock) -> Result<Megahertz> {
+        self.device.applications_clock(clock_type).map(Megahertz)
+    }
+
+    /// `Device.default_applications_clock()`.
+    #[inline]
+    pub fn default_applications_clock(&self, clock_type: Clock) -> Result<Megahertz> {
+        self.device.default_applications_clock(clock_type).map(Megahertz)
+    }
+
+    /// `Device.temperature()`.
+    #[inline]
+    pub fn temperature(&self, sensor: TemperatureSensor) -> Result<Celsius> {
+        self.device.temperature(sensor).map(Celsius::from_unsigned)
+    }
+
+    /// `Device.temperature_threshold()`.
+    #[inline]
+    pub fn temperature_threshold(&self, threshold_type: TemperatureThreshold) -> Result<Celsius> {
+        self.device.temperature_threshold(threshold_type).map(Celsius::from_unsigned)
+    }
+
+    /// `Device.memory_info()`.
+    #[inline]
+    pub fn memory_info(&self) -> Result<MemoryUsage> {
+        self.device.memory_info().map(MemoryUsage::from)
+    }
+
+    /// `Device.bar1_memory_info()`.
+    #[inline]
+    pub fn bar1_memory_info(&self) -> Result<MemoryUsage> {
+        self.device.bar1_memory_info().map(MemoryUsage::from)
+    }
+
+    /// `Device.utilization_rates()`.
+    #[inline]
+    pub fn utilization_rates(&self) -> Result<UtilizationRates> {
+        self.device.utilization_rates().map(UtilizationRates::from)
+    }
+
+    /// `Device.fan_speed()`.
+    #[inline]
+    pub fn fan_speed(&self) -> Result<Percent> {
+        self.device.fan_speed().map(Percent)
+    }
+
+    /// `Device.fan_speed_for()`.
+    #[inline]
+    pub fn fan_speed_for(&self, fan_idx: u32) -> Result<Percent> {
+        self.device.fan_speed_for(fan_idx).map(Percent)
+    }
+}
+
+/**
+Typed view of a `Unit`.
+
+Obtain this via `Unit.typed()`. Each method calls the corresponding `Unit`
+method and wraps the result in its unit; see those methods for errors and
+device support.
+*/
+#[derive(Debug)]
+pub struct TypedUnit<'unit, 'nvml: 'unit> {
+    pub(crate) unit: &'unit Unit<'nvml>
+}
+
+impl<'unit, 'nvml: 'unit> TypedUnit<'unit, 'nvml> {
+    /// Obtain the `Unit` reference stored within this struct.
+    #[inline]
+    pub fn unit(&self) -> &Unit<'nvml> {
+        self.unit
+    }
+
+    /// `Unit.temperature()`.
+    #[inline]
+    pub fn temperature(&self, reading_type: TemperatureReading) -> Result<Celsius> {
+        self.unit.temperature(reading_type).map(Celsius::from_unsigned)
+    }
+
+    /// `Unit.psu_info().power_draw`, which NVML reports in watts.
+    #[inline]
+    pub fn psu_power_draw(&self) -> Result<Milliwatts> {
+        self.unit.psu_info().map(|info| Milliwatts::from_watts(info.power_draw))
+    }
+}
+
+#[cfg(test)]
+#[deny(unused_mut)]
+mod test {
+    use super::*;
+    use test_utils::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Milliwatts::from_watts(250), Milliwatts(250_000));
+        assert_eq!(Milliwatts(1500).as_watts(), 1.5);
+        assert_eq!(Megahertz(1530).as_hertz(), 1_530_000_000);
+        assert_eq!(Celsius(100).as_fahrenheit(), 212.0);
+        assert_eq!(Bytes::from_mib(2).as_kib(), 2048.0);
+        assert_eq!(Bytes(3 * 1024 * 1024 * 1024).as_gib(), 3.0);
+        assert_eq!(Percent(25).as_fraction(), 0.25);
+        assert_eq!(u32::from(Milliwatts(7)), 7);
+    }
+
+    #[test]
+    fn arithmetic_and_display() {
+        assert_eq!(Milliwatts(300) - Milliwatts(100), Milliwatts(200));
+        assert_eq!(Milliwatts(100) - Milliwatts(300), Milliwatts(0));
+        assert_eq!(Milliwatts(100).checked_sub(Milliwatts(300)), None);
+        assert_eq!(Milliwatts::from_watts(u32::max_value()), Milliwatts(u32::max_value()));
+        assert_eq!(Celsius(-5) - Celsius(10), Celsius(-15));
+        assert_eq!(Bytes(1) + Bytes(2), Bytes(3));
+        assert_eq!(Milliwatts(250_000).to_string(), "250000 mW");
+        assert_eq!(Megahertz(1530).to_string(), "1530 MHz");
+        assert_eq!(Celsius(65).to_string(), "65 °C");
+        assert_eq!(Celsius(-10).to_string(), "-10 °C");
+        assert_eq!(Bytes(512).to_string(), "512 B");
+        assert_eq!(Percent(42).to_string(), "42 %");
+    }
+
+    #[test]
+    fn power_limits() {
+        let limits = PowerLimits::from(PowerManagementConstraints {
+            min_limit: 100_000,
+            max_limit: 300_000
+        });
+
+        assert!(limits.contains(Milliwatts(100_000)));
+        assert!(limits.contains(Milliwatts(300_000)));
+        assert!(!limits.contains(Milliwatts::from_watts(301)));
+    }
+
+    #[test]
+    fn typed_device() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let typed = device.typed();
+            typed.power_usage()?;
+            typed.power_management_limit_constraints()?;
+            typed.clock_info(Clock::Graphics)?;
+            typed.temperature(TemperatureSensor::Gpu)?;
+            typed.memory_info()?;
+            typed.utilization_rates()?;
+            Ok(())
+        })
+    }
+}
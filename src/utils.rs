@@ -0,0 +1,39 @@
+// Helpers shared across the crate.
+
+use error::{Error, ErrorKind, Result};
+
+// Maps `NotSupported` to `None`, for features that are reported when the
+// `Device` has them and skipped when it does not.
+pub(crate) fn optional<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(Error(ErrorKind::NotSupported, _)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Text for boolean states, as nvidia-smi writes them.
+
+pub(crate) fn on_off(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+pub(crate) fn enabled_disabled(value: bool) -> &'static str {
+    if value {
+        "Enabled"
+    } else {
+        "Disabled"
+    }
+}
+
+pub(crate) fn yes_no(value: bool) -> &'static str {
+    if value {
+        "Yes"
+    } else {
+        "No"
+    }
+}